@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(async_from(Comment))]
+struct CommentDto {
+    id: i64,
+    #[convert(rename = author_id, await_with = find_user_name)]
+    author: String,
+    text: String,
+}
+
+struct Comment {
+    id: i64,
+    author_id: i64,
+    text: String,
+}
+
+/// In-memory "repository" lookup
+async fn find_user_name(id: i64) -> String {
+    let users = HashMap::from([(1, "Jack"), (2, "Jill")]);
+    users.get(&id).copied().unwrap_or("unknown").to_string()
+}
+
+/// Waker which does nothing, the future is polled in a loop
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Poll the future to completion on the current thread
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+fn main() {}
+
+#[test]
+fn test_async_from() {
+    use just_convert::AsyncFrom;
+
+    fn assert_send<F: Send>(future: F) -> F {
+        future
+    }
+
+    let comment = Comment {
+        id: 10,
+        author_id: 2,
+        text: "Hello".to_string(),
+    };
+    let dto = block_on(assert_send(CommentDto::async_from(comment)));
+    assert_eq!(
+        CommentDto {
+            id: 10,
+            author: "Jill".to_string(),
+            text: "Hello".to_string(),
+        },
+        dto
+    );
+}
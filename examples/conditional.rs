@@ -0,0 +1,83 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(User))]
+struct PublicUser {
+    name: String,
+    // hidden for private profiles
+    #[convert(skip_if = "this.is_private")]
+    email: String,
+    #[convert(map_if(cond = ". < 18", then = "\"minor\".to_string()", else = ".to_string()"))]
+    age: String,
+}
+
+struct User {
+    name: String,
+    email: String,
+    age: u8,
+    is_private: bool,
+}
+
+// the age is clamped differently in each direction
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(ProfileRow))]
+struct Profile {
+    #[convert(map_if(
+        from(cond = ". < 0", then = "0", else = ". as u32"),
+        into(ProfileRow, cond = ". > 150", then = "150", else = ". as i64"),
+    ))]
+    age: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct ProfileRow {
+    age: i64,
+}
+
+fn main() {}
+
+#[test]
+fn test_conditional() {
+    let public: PublicUser = User {
+        name: "Jack".to_string(),
+        email: "jack@example.com".to_string(),
+        age: 16,
+        is_private: true,
+    }
+    .into();
+    assert_eq!(
+        PublicUser {
+            name: "Jack".to_string(),
+            email: String::new(),
+            age: "minor".to_string(),
+        },
+        public
+    );
+
+    let public: PublicUser = User {
+        name: "Jill".to_string(),
+        email: "jill@example.com".to_string(),
+        age: 30,
+        is_private: false,
+    }
+    .into();
+    assert_eq!(
+        PublicUser {
+            name: "Jill".to_string(),
+            email: "jill@example.com".to_string(),
+            age: "30".to_string(),
+        },
+        public
+    );
+}
+
+#[test]
+fn test_map_if_specialised() {
+    assert_eq!(Profile { age: 0 }, Profile::from(ProfileRow { age: -5 }));
+    assert_eq!(Profile { age: 40 }, Profile::from(ProfileRow { age: 40 }));
+
+    let row: ProfileRow = Profile { age: 200 }.into();
+    assert_eq!(ProfileRow { age: 150 }, row);
+}
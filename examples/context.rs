@@ -0,0 +1,87 @@
+#![allow(dead_code)]
+
+use std::cell::Cell;
+
+use just_convert::JustConvert;
+
+struct Context {
+    base_url: String,
+    next_id: Cell<i64>,
+}
+
+impl Context {
+    fn allocate_id(&self) -> i64 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+}
+
+fn absolute_url(path: String, ctx: &Context) -> String {
+    format!("{}{}", ctx.base_url, path)
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(NewImage, context = Context))]
+#[convert(into(ImageDto, context = Context))]
+struct Image {
+    #[convert(map(from = "ctx.allocate_id()"))]
+    id: i64,
+    #[convert(with(into = absolute_url))]
+    path: String,
+    #[convert(map(into = ".to_uppercase()"))]
+    #[convert(skip(from))]
+    title: String,
+}
+
+struct NewImage {
+    path: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct ImageDto {
+    id: i64,
+    path: String,
+    title: String,
+}
+
+fn main() {}
+
+#[test]
+fn test_context() {
+    use just_convert::{FromWithContext, IntoWithContext};
+
+    let ctx = Context {
+        base_url: "https://example.com".to_string(),
+        next_id: Cell::new(7),
+    };
+
+    let image = Image::from_with_context(
+        NewImage {
+            path: "/cat.png".to_string(),
+        },
+        &ctx,
+    );
+    assert_eq!(
+        Image {
+            id: 7,
+            path: "/cat.png".to_string(),
+            title: String::new(),
+        },
+        image
+    );
+
+    let dto: ImageDto = Image {
+        title: "cat".to_string(),
+        ..image
+    }
+    .into_with_context(&ctx);
+    assert_eq!(
+        ImageDto {
+            id: 7,
+            path: "https://example.com/cat.png".to_string(),
+            title: "CAT".to_string(),
+        },
+        dto
+    );
+}
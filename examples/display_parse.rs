@@ -0,0 +1,107 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OrderId(u64);
+
+impl std::fmt::Display for OrderId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ord-{}", self.0)
+    }
+}
+
+impl std::str::FromStr for OrderId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.strip_prefix("ord-").ok_or("expected `ord-` prefix")?;
+        id.parse()
+            .map(OrderId)
+            .map_err(|_| format!("invalid id `{id}`"))
+    }
+}
+
+// strings as they come from a form or a CSV file
+#[derive(Debug, PartialEq)]
+struct OrderForm {
+    id: String,
+    total: String,
+    discount: Option<String>,
+    quantities: Vec<String>,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(try_from(OrderForm), into(OrderForm))]
+struct Order {
+    #[convert(parse(from), display(into))]
+    id: OrderId,
+    #[convert(parse(from), display(into))]
+    total: f64,
+    #[convert(parse(from), display(into))]
+    discount: Option<f64>,
+    // every element is parsed, the error names its index
+    #[convert(parse(from), display(into))]
+    quantities: Vec<u32>,
+}
+
+fn main() {}
+
+#[test]
+fn test_display_parse() {
+    let form = OrderForm {
+        id: "ord-42".to_string(),
+        total: "19.5".to_string(),
+        discount: None,
+        quantities: vec!["1".to_string(), "3".to_string()],
+    };
+    let order = Order::try_from(form).unwrap();
+    assert_eq!(
+        Order {
+            id: OrderId(42),
+            total: 19.5,
+            discount: None,
+            quantities: vec![1, 3],
+        },
+        order
+    );
+
+    let form: OrderForm = order.into();
+    assert_eq!(
+        OrderForm {
+            id: "ord-42".to_string(),
+            total: "19.5".to_string(),
+            discount: None,
+            quantities: vec!["1".to_string(), "3".to_string()],
+        },
+        form
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    let form = OrderForm {
+        id: "42".to_string(),
+        total: "19.5".to_string(),
+        discount: None,
+        quantities: vec![],
+    };
+    let err = Order::try_from(form).unwrap_err();
+    assert_eq!(
+        "can not parse field `id` converting OrderForm -> Order: expected `ord-` prefix",
+        err.to_string()
+    );
+
+    let form = OrderForm {
+        id: "ord-42".to_string(),
+        total: "19.5".to_string(),
+        discount: Some("0.5".to_string()),
+        quantities: vec!["1".to_string(), "x".to_string()],
+    };
+    let err = Order::try_from(form).unwrap_err();
+    assert_eq!(
+        "can not parse field `quantities[1]` converting OrderForm -> Order: \
+         invalid digit found in string",
+        err.to_string()
+    );
+}
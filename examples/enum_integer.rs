@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(i32), into(i32))]
+enum Status {
+    Active = 1,
+    // implicit discriminant 2
+    Blocked,
+    #[convert(value = 10)]
+    Deleted,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(u8))]
+enum Priority {
+    Low,
+    High,
+    // unknown values land here instead of an error,
+    // the value itself is lost: `Unknown` is converted back into its discriminant
+    #[convert(other)]
+    Unknown,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_integer() {
+    use just_convert::UnknownVariant;
+
+    assert_eq!(Ok(Status::Active), Status::try_from(1));
+    assert_eq!(Ok(Status::Blocked), Status::try_from(2));
+    assert_eq!(Ok(Status::Deleted), Status::try_from(10));
+
+    let err = Status::try_from(3).unwrap_err();
+    assert_eq!(UnknownVariant::new("Status", 3), err);
+    assert_eq!("unknown `Status` value `3`", err.to_string());
+
+    assert_eq!(1, i32::from(Status::Active));
+    assert_eq!(2, i32::from(Status::Blocked));
+    assert_eq!(10, i32::from(Status::Deleted));
+}
+
+#[test]
+fn test_enum_integer_other() {
+    assert_eq!(Priority::High, Priority::from(1));
+    assert_eq!(Priority::Unknown, Priority::from(7));
+    assert_eq!(2, u8::from(Priority::Unknown));
+}
@@ -0,0 +1,97 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Active,
+    Blocked,
+}
+
+// protobuf style enum, `Unspecified` stands for the missing status
+#[derive(JustConvert, Debug, Clone, Copy, PartialEq)]
+#[convert(from_into(Status), from_into(i32))]
+enum ProtoStatus {
+    #[convert(none)]
+    Unspecified = 0,
+    Active = 1,
+    Blocked = 2,
+}
+
+struct UserProto {
+    name: String,
+    status: ProtoStatus,
+    history: Vec<ProtoStatus>,
+    pending: Option<Vec<ProtoStatus>>,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(UserProto))]
+struct User {
+    name: String,
+    // the whole `Option` is converted, `None` is `ProtoStatus::Unspecified`
+    #[convert(none_variant)]
+    status: Option<Status>,
+    // every element is converted as a whole
+    #[convert(none_variant)]
+    history: Vec<Option<Status>>,
+    #[convert(none_variant)]
+    pending: Option<Vec<Option<Status>>>,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_none() {
+    assert_eq!(ProtoStatus::Unspecified, ProtoStatus::from(None::<Status>));
+    assert_eq!(
+        ProtoStatus::Blocked,
+        ProtoStatus::from(Some(Status::Blocked))
+    );
+    assert_eq!(ProtoStatus::Active, ProtoStatus::from(Status::Active));
+
+    assert_eq!(None, Option::<Status>::from(ProtoStatus::Unspecified));
+    assert_eq!(
+        Some(Status::Active),
+        Option::<Status>::from(ProtoStatus::Active)
+    );
+
+    // the plain conversion fails for the `none` variant
+    assert_eq!(Ok(Status::Active), Status::try_from(ProtoStatus::Active));
+    let err = Status::try_from(ProtoStatus::Unspecified).unwrap_err();
+    assert_eq!(
+        "unknown `Status` value `ProtoStatus::Unspecified`",
+        err.to_string()
+    );
+
+    // integer conversions keep the variant as is
+    assert_eq!(Ok(ProtoStatus::Unspecified), ProtoStatus::try_from(0));
+}
+
+#[test]
+fn test_enum_none_field() {
+    let proto = UserProto {
+        name: "John".to_string(),
+        status: ProtoStatus::Unspecified,
+        history: vec![ProtoStatus::Active, ProtoStatus::Unspecified],
+        pending: Some(vec![ProtoStatus::Unspecified]),
+    };
+    let user = User::from(proto);
+    assert_eq!(None, user.status);
+    assert_eq!(vec![Some(Status::Active), None], user.history);
+    assert_eq!(Some(vec![None]), user.pending);
+
+    let user = User {
+        name: "John".to_string(),
+        status: Some(Status::Blocked),
+        history: vec![None, Some(Status::Blocked)],
+        pending: None,
+    };
+    let proto: UserProto = user.into();
+    assert_eq!(ProtoStatus::Blocked, proto.status);
+    assert_eq!(
+        vec![ProtoStatus::Unspecified, ProtoStatus::Blocked],
+        proto.history
+    );
+    assert_eq!(None, proto.pending);
+}
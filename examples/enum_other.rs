@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+mod external {
+    pub enum Shape {
+        Circle { radius: f32 },
+        Square(f32),
+        Triangle,
+        Hexagon,
+    }
+
+    #[derive(Debug, PartialEq)]
+    pub enum Level {
+        Low,
+        High,
+        Critical,
+    }
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(external::Shape))]
+enum Shape {
+    Circle {
+        radius: f64,
+    },
+    Square(f64),
+    // shapes we don't know land here
+    #[convert(other)]
+    Unknown,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(external::Level))]
+enum Level {
+    Low,
+    High,
+    // keeps the raw value, so it converts back losslessly
+    #[convert(other)]
+    Other(external::Level),
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(String), into(String))]
+enum Color {
+    Red,
+    #[convert(other)]
+    Custom(String),
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(u16))]
+enum StatusCode {
+    // discriminants cannot be cast from an enum with fields
+    #[convert(value = 200)]
+    Ok,
+    #[convert(value = 404)]
+    NotFound,
+    #[convert(other)]
+    Raw(u16),
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+// unknown strings land on `Unknown`, but every level must be mapped
+#[convert(from(String), from(external::Level, exhaustive))]
+enum Severity {
+    Low,
+    High,
+    Critical,
+    #[convert(other)]
+    Unknown,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_other() {
+    use external::Shape as Ext;
+
+    assert_eq!(
+        Shape::Circle { radius: 2.0 },
+        Ext::Circle { radius: 2.0 }.into()
+    );
+    assert_eq!(Shape::Square(1.5), Ext::Square(1.5).into());
+    assert_eq!(Shape::Unknown, Ext::Triangle.into());
+    assert_eq!(Shape::Unknown, Ext::Hexagon.into());
+}
+
+#[test]
+fn test_enum_other_raw_value() {
+    use external::Level as Ext;
+
+    assert_eq!(Level::High, Level::from(Ext::High));
+    assert_eq!(Level::Other(Ext::Critical), Level::from(Ext::Critical));
+    assert_eq!(Ext::Critical, Ext::from(Level::Other(Ext::Critical)));
+
+    assert_eq!(Color::Red, Color::from("Red"));
+    assert_eq!(Color::Custom("teal".to_string()), Color::from("teal"));
+    assert_eq!("teal", Color::Custom("teal".to_string()).to_string());
+
+    assert_eq!(StatusCode::NotFound, StatusCode::from(404));
+    assert_eq!(StatusCode::Raw(500), StatusCode::from(500));
+    assert_eq!(500, u16::from(StatusCode::Raw(500)));
+}
+
+#[test]
+fn test_enum_exhaustive() {
+    use external::Level as Ext;
+
+    assert_eq!(Severity::Critical, Severity::from(Ext::Critical));
+    assert_eq!(Severity::Unknown, Severity::from("Medium"));
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(String, rename_all = "snake_case"))]
+enum Status {
+    Active,
+    OnHold,
+    #[convert(rename = "removed", alias("deleted", "archived"))]
+    Deleted,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(String, case_insensitive), into(String))]
+enum Color {
+    Red,
+    Green,
+    // unknown names land here instead of an error
+    #[convert(other)]
+    Unknown,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_string() {
+    use just_convert::UnknownVariant;
+
+    assert_eq!(Ok(Status::Active), "active".parse());
+    assert_eq!(Ok(Status::OnHold), Status::try_from("on_hold"));
+    assert_eq!(Ok(Status::Deleted), Status::try_from("removed".to_string()));
+    assert_eq!(Ok(Status::Deleted), "archived".parse());
+
+    let err = "Active".parse::<Status>().unwrap_err();
+    assert_eq!(UnknownVariant::new("Status", "Active"), err);
+
+    assert_eq!("on_hold", Status::OnHold.to_string());
+    assert_eq!("removed", String::from(Status::Deleted));
+    assert_eq!("active  ", format!("{:8}", Status::Active));
+}
+
+#[test]
+fn test_enum_string_case_insensitive() {
+    assert_eq!(Color::Red, Color::from("RED"));
+    assert_eq!(Color::Green, Color::from("green".to_string()));
+    assert_eq!(Color::Unknown, Color::from("blue"));
+    assert_eq!("Unknown", Color::Unknown.to_string());
+}
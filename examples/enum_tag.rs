@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(Debug, PartialEq)]
+struct ShapeRow {
+    kind: String,
+    radius: Option<f64>,
+    width: Option<f64>,
+    height: Option<f64>,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(ShapeRow, tag = kind, rename_all = "snake_case"))]
+enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    // unnamed fields are mapped to the struct fields by `rename`
+    Square(#[convert(rename = width)] f64),
+    Empty,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_tag() {
+    let row = ShapeRow::from(Shape::Rectangle {
+        width: 2.0,
+        height: 3.0,
+    });
+    assert_eq!(
+        ShapeRow {
+            kind: "rectangle".to_string(),
+            radius: None,
+            width: Some(2.0),
+            height: Some(3.0),
+        },
+        row
+    );
+    assert_eq!(
+        Shape::Rectangle {
+            width: 2.0,
+            height: 3.0
+        },
+        Shape::try_from(row).unwrap()
+    );
+
+    let row = ShapeRow::from(Shape::Square(1.5));
+    assert_eq!(("square", Some(1.5)), (row.kind.as_str(), row.width));
+    assert_eq!(Shape::Square(1.5), Shape::try_from(row).unwrap());
+
+    let row = ShapeRow::from(Shape::Empty);
+    assert_eq!(Shape::Empty, Shape::try_from(row).unwrap());
+}
+
+#[test]
+fn test_enum_tag_errors() {
+    let row = ShapeRow {
+        kind: "circle".to_string(),
+        radius: None,
+        width: None,
+        height: None,
+    };
+    let err = Shape::try_from(row).unwrap_err();
+    assert_eq!("missing `radius` for `kind` `circle`", err.to_string());
+
+    let row = ShapeRow {
+        kind: "triangle".to_string(),
+        radius: None,
+        width: None,
+        height: None,
+    };
+    let err = Shape::try_from(row).unwrap_err();
+    assert_eq!("unknown `Shape` value `triangle`", err.to_string());
+}
@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Created(CreatedEvent),
+    Renamed { id: u64, new_name: String },
+    Deleted { id: u64 },
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(into(Event::Created))]
+struct CreatedEvent {
+    id: u64,
+    name: String,
+}
+
+// struct variants are converted field by field with the usual field options
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(into(Event::Renamed, fields))]
+struct RenamedEvent {
+    id: u64,
+    #[convert(rename = new_name)]
+    name: String,
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_variant() {
+    let created = CreatedEvent {
+        id: 1,
+        name: "first".to_string(),
+    };
+    let event = Event::from(created);
+    assert_eq!(
+        Event::Created(CreatedEvent {
+            id: 1,
+            name: "first".to_string()
+        }),
+        event
+    );
+    assert_eq!(
+        CreatedEvent {
+            id: 1,
+            name: "first".to_string()
+        },
+        CreatedEvent::try_from(event).unwrap()
+    );
+
+    let err = CreatedEvent::try_from(Event::Deleted { id: 1 }).unwrap_err();
+    assert_eq!("expected the `Event::Created` variant", err.to_string());
+}
+
+#[test]
+fn test_enum_struct_variant() {
+    let renamed = RenamedEvent {
+        id: 2,
+        name: "second".to_string(),
+    };
+    let event = Event::from(renamed);
+    assert_eq!(
+        Event::Renamed {
+            id: 2,
+            new_name: "second".to_string()
+        },
+        event
+    );
+
+    let renamed = RenamedEvent::try_from(event).unwrap();
+    assert_eq!("second", renamed.name);
+    assert!(RenamedEvent::try_from(Event::Deleted { id: 2 }).is_err());
+}
@@ -0,0 +1,52 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+mod external {
+    #[derive(Debug, PartialEq)]
+    pub enum JobState {
+        Pending,
+        Queued,
+        Waiting,
+        Running { progress: u8 },
+        Succeeded,
+        Failed(String),
+    }
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from_into(external::JobState))]
+enum JobState {
+    // all three are converted into `Waiting`, which goes back as `Queued`
+    #[convert(from_into(external::JobState, variants(Pending, Queued, Waiting), canonical = Queued))]
+    Waiting,
+    Running {
+        progress: u8,
+    },
+    #[convert(from_into(external::JobState, variants(Succeeded)))]
+    Done,
+    Failed(String),
+}
+
+fn main() {}
+
+#[test]
+fn test_enum_variants() {
+    use external::JobState as Ext;
+
+    assert_eq!(JobState::Waiting, Ext::Pending.into());
+    assert_eq!(JobState::Waiting, Ext::Queued.into());
+    assert_eq!(JobState::Waiting, Ext::Waiting.into());
+    assert_eq!(
+        JobState::Running { progress: 50 },
+        Ext::Running { progress: 50 }.into()
+    );
+    assert_eq!(JobState::Done, Ext::Succeeded.into());
+
+    assert_eq!(Ext::Queued, JobState::Waiting.into());
+    assert_eq!(Ext::Succeeded, JobState::Done.into());
+    assert_eq!(
+        Ext::Failed("oops".into()),
+        JobState::Failed("oops".into()).into()
+    );
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+// Generate UserDto struct and conversions between User and UserDto
+#[derive(JustConvert, Default)]
+#[convert(generate(UserDto, derive(Debug, Clone, PartialEq), vis = pub))]
+struct User {
+    id: i64,
+    #[convert(rename = user_name)]
+    name: String,
+    // not a part of UserDto, default value when converting from it
+    #[convert(skip)]
+    password: String,
+    // another type in UserDto
+    #[convert(ty = Option<String>)]
+    email: Option<Email>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Email(String);
+
+impl From<Email> for String {
+    fn from(value: Email) -> Self {
+        value.0
+    }
+}
+
+impl From<String> for Email {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+fn main() {}
+
+#[test]
+fn test_generate() {
+    let user = User {
+        id: 1,
+        name: "Jack".to_string(),
+        password: "secret".to_string(),
+        email: Some(Email("jack@example.com".to_string())),
+    };
+    let dto: UserDto = user.into();
+    debug_assert_eq!(
+        UserDto {
+            id: 1,
+            user_name: "Jack".to_string(),
+            email: Some("jack@example.com".to_string()),
+        },
+        dto
+    );
+
+    let user: User = dto.into();
+    assert_eq!(user.password, "");
+    assert_eq!(user.email, Some(Email("jack@example.com".to_string())));
+}
@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(CreateUser, after = normalize))]
+#[convert(into(UserRow, validate = validate_row))]
+struct User {
+    email: String,
+    age: u8,
+}
+
+struct CreateUser {
+    email: String,
+    age: u8,
+}
+
+#[derive(Debug, PartialEq)]
+struct UserRow {
+    email: String,
+    age: u8,
+}
+
+fn normalize(user: &mut User) {
+    user.email = user.email.trim().to_lowercase();
+}
+
+fn validate_row(row: &UserRow) -> Result<(), String> {
+    if !(18..=120).contains(&row.age) {
+        return Err(format!("age {} is out of range", row.age));
+    }
+    Ok(())
+}
+
+fn main() {}
+
+#[test]
+fn test_after() {
+    let user: User = CreateUser {
+        email: " Jack@Example.COM ".to_string(),
+        age: 30,
+    }
+    .into();
+    assert_eq!(
+        User {
+            email: "jack@example.com".to_string(),
+            age: 30,
+        },
+        user
+    );
+}
+
+#[test]
+fn test_validate() {
+    let row: UserRow = User {
+        email: "jack@example.com".to_string(),
+        age: 30,
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(30, row.age);
+
+    let err = TryInto::<UserRow>::try_into(User {
+        email: "jack@example.com".to_string(),
+        age: 7,
+    })
+    .unwrap_err();
+    assert_eq!("age 7 is out of range", err.to_string());
+}
@@ -0,0 +1,120 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(Debug, PartialEq)]
+struct SensorRow {
+    id: i64,
+    level: u8,
+    temperature: f32,
+    readings: Vec<i16>,
+    limit: Option<u16>,
+}
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(try_from(SensorRow), into(SensorRow))]
+struct Sensor {
+    // the id must fit, otherwise the conversion fails, `u32` fits into `i64` as is
+    #[convert(checked(from))]
+    id: u32,
+    // out of range levels are clamped to 0..=255
+    #[convert(saturating)]
+    level: i32,
+    #[convert(cast)]
+    temperature: f64,
+    // every element is checked, the error names its index
+    #[convert(checked(from), saturating(into))]
+    readings: Vec<u8>,
+    #[convert(wrapping)]
+    limit: Option<u8>,
+}
+
+fn main() {}
+
+#[test]
+fn test_numeric() {
+    let row = SensorRow {
+        id: 7,
+        level: 200,
+        temperature: 21.5,
+        readings: vec![1, 2, 3],
+        limit: Some(258),
+    };
+    let sensor = Sensor::try_from(row).unwrap();
+    assert_eq!(
+        Sensor {
+            id: 7,
+            level: 200,
+            temperature: 21.5,
+            readings: vec![1, 2, 3],
+            limit: Some(2),
+        },
+        sensor
+    );
+
+    let sensor = Sensor {
+        id: 7,
+        level: -20,
+        temperature: 0.1,
+        readings: vec![255, 0],
+        limit: None,
+    };
+    let row: SensorRow = sensor.into();
+    assert_eq!(
+        SensorRow {
+            id: 7,
+            level: 0,
+            temperature: 0.1f64 as f32,
+            readings: vec![255, 0],
+            limit: None,
+        },
+        row
+    );
+}
+
+#[test]
+fn test_numeric_out_of_range() {
+    let row = SensorRow {
+        id: -1,
+        level: 0,
+        temperature: 0.0,
+        readings: vec![],
+        limit: None,
+    };
+    let err = Sensor::try_from(row).unwrap_err();
+    assert_eq!(
+        "value `-1` of field `id` is out of range of `u32`",
+        err.to_string()
+    );
+
+    let row = SensorRow {
+        id: 1,
+        level: 0,
+        temperature: 0.0,
+        readings: vec![10, 300, -5],
+        limit: None,
+    };
+    let err = Sensor::try_from(row).unwrap_err();
+    assert_eq!(
+        "value `300` of field `readings[1]` is out of range of `u8`",
+        err.to_string()
+    );
+}
+
+#[test]
+fn test_num_convert() {
+    use just_convert::NumConvert;
+
+    assert_eq!(127i8, NumConvert::<i8>::saturating(1000i32));
+    assert_eq!(-128i8, NumConvert::<i8>::saturating(-1000i32));
+    assert_eq!(0u8, NumConvert::<u8>::saturating(-1i64));
+    assert_eq!(232u8, NumConvert::<u8>::wrapping(1000u16));
+    assert_eq!(255u8, NumConvert::<u8>::saturating(1e9f64));
+    assert_eq!(f32::MAX, NumConvert::<f32>::saturating(1e300f64));
+    assert_eq!(Ok(255u8), NumConvert::<u8>::checked(255.9f32));
+    assert!(NumConvert::<u8>::checked(256.0f32).is_err());
+    assert!(NumConvert::<i32>::checked(f64::NAN).is_err());
+    assert!(NumConvert::<i64>::checked(9.3e18f64).is_err());
+    assert!(NumConvert::<f32>::checked(u128::MAX).is_err());
+    assert_eq!(Ok(f32::INFINITY), NumConvert::<f32>::checked(f64::INFINITY));
+}
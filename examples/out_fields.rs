@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(User))]
+struct UserDto {
+    // built from the converted `first_name` and `last_name`
+    #[convert(map = "format!(\"{} {}\", out.first_name, out.last_name)")]
+    display_name: String,
+    #[convert(map = ".trim().to_string()")]
+    first_name: String,
+    last_name: String,
+}
+
+struct User {
+    first_name: String,
+    last_name: String,
+}
+
+#[derive(JustConvert)]
+#[convert(into(Label))]
+struct Tag {
+    // `ty` gives the type of `out.name`, so its methods can be called
+    #[convert(ty = String)]
+    name: &'static str,
+    #[convert(map = "out.name.len()")]
+    size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct Label {
+    name: String,
+    size: usize,
+}
+
+fn main() {}
+
+#[test]
+fn test_out_fields() {
+    let dto: UserDto = User {
+        first_name: " Jack ".to_string(),
+        last_name: "Smith".to_string(),
+    }
+    .into();
+    assert_eq!(
+        UserDto {
+            display_name: "Jack Smith".to_string(),
+            first_name: "Jack".to_string(),
+            last_name: "Smith".to_string(),
+        },
+        dto
+    );
+}
+
+#[test]
+fn test_out_fields_into() {
+    let label: Label = Tag {
+        name: "urgent",
+        size: 0,
+    }
+    .into();
+    assert_eq!(
+        Label {
+            name: "urgent".to_string(),
+            size: 6,
+        },
+        label
+    );
+}
@@ -0,0 +1,37 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(Person))]
+struct PersonDto {
+    first: String,
+    last: String,
+    // reads `first` and `last`, so it is converted before they are moved
+    #[convert(map = "format!(\"{} {}\", this.first, this.last)")]
+    full_name: String,
+}
+
+struct Person {
+    first: String,
+    last: String,
+}
+
+fn main() {}
+
+#[test]
+fn test_partial_move() {
+    let dto: PersonDto = Person {
+        first: "Jack".to_string(),
+        last: "Smith".to_string(),
+    }
+    .into();
+    assert_eq!(
+        PersonDto {
+            first: "Jack".to_string(),
+            last: "Smith".to_string(),
+            full_name: "Jack Smith".to_string(),
+        },
+        dto
+    );
+}
@@ -0,0 +1,46 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+// Generate UserPatch with all fields optional and User::apply_patch
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(patch(UserPatch, derive(Default)))]
+struct User {
+    // ids are never patched
+    #[convert(skip)]
+    id: i64,
+    #[convert(rename = user_name)]
+    name: String,
+    #[convert(map(from(UserPatch, ".to_lowercase()")))]
+    email: String,
+    // None in the patch keeps the value, Some(None) clears it
+    phone: Option<String>,
+}
+
+fn main() {}
+
+#[test]
+fn test_apply_patch() {
+    let mut user = User {
+        id: 1,
+        name: "Jack".to_string(),
+        email: "jack@example.com".to_string(),
+        phone: Some("123".to_string()),
+    };
+
+    user.apply_patch(UserPatch {
+        email: Some("JACK@EXAMPLE.ORG".to_string()),
+        phone: Some(None),
+        ..Default::default()
+    });
+
+    debug_assert_eq!(
+        User {
+            id: 1,
+            name: "Jack".to_string(),
+            email: "jack@example.org".to_string(),
+            phone: None,
+        },
+        user
+    );
+}
@@ -0,0 +1,71 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(B))]
+#[convert(try_from(C))]
+struct A {
+    // panic with "field `id` missing converting B -> A"
+    #[convert(unwrap(from(B, expect)))]
+    // return an error with the same message
+    #[convert(unwrap(from(C, required)))]
+    id: i64,
+    #[convert(unwrap = default)]
+    name: String,
+    #[convert(unwrap_or = "\"guest\".to_string()")]
+    role: String,
+}
+
+struct B {
+    id: Option<i64>,
+    name: Option<String>,
+    role: Option<String>,
+}
+
+struct C {
+    id: Option<i64>,
+    name: Option<String>,
+    role: Option<String>,
+}
+
+fn main() {}
+
+#[test]
+fn test_unwrap() {
+    let a: A = B {
+        id: Some(1),
+        name: None,
+        role: None,
+    }
+    .into();
+    debug_assert_eq!(
+        A {
+            id: 1,
+            name: String::new(),
+            role: "guest".to_string(),
+        },
+        a
+    );
+
+    let result = std::panic::catch_unwind(|| {
+        let _: A = B {
+            id: None,
+            name: None,
+            role: None,
+        }
+        .into();
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_unwrap_required() {
+    let c = C {
+        id: None,
+        name: Some("Jack".to_string()),
+        role: Some("admin".to_string()),
+    };
+    let err = A::try_from(c).unwrap_err();
+    assert_eq!(err.to_string(), "field `id` missing converting C -> A");
+}
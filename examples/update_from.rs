@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+// Merge UserDto into already loaded User, keeping id and created_at
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(UserDto))]
+struct User {
+    #[convert(skip)]
+    id: i64,
+    #[convert(rename = user_name)]
+    name: String,
+    #[convert(map = ".to_lowercase()")]
+    email: String,
+    #[convert(skip)]
+    created_at: u64,
+}
+
+struct UserDto {
+    user_name: String,
+    email: String,
+}
+
+fn main() {}
+
+#[test]
+fn test_update_from() {
+    use just_convert::UpdateFrom;
+
+    let mut user = User {
+        id: 1,
+        name: "Jack".to_string(),
+        email: "jack@example.com".to_string(),
+        created_at: 100,
+    };
+
+    user.update_from(UserDto {
+        user_name: "John".to_string(),
+        email: "JOHN@EXAMPLE.COM".to_string(),
+    });
+
+    debug_assert_eq!(
+        User {
+            id: 1,
+            name: "John".to_string(),
+            email: "john@example.com".to_string(),
+            created_at: 100,
+        },
+        user
+    );
+}
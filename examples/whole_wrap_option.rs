@@ -2,8 +2,10 @@
 
 use just_convert::JustConvert;
 
+// Wrap every plain value of B into Some, and unwrap them back into B
 #[derive(JustConvert)]
 #[convert(from(B, wrap_option))]
+#[convert(into(B, unwrap_option))]
 struct A {
     id: Option<i64>,
     abc: Option<i64>,
@@ -17,4 +19,62 @@ struct B {
     def: i64,
 }
 
+// Wrap every plain value of C into Some of D
+#[derive(JustConvert)]
+#[convert(into(D, wrap_option))]
+struct C {
+    id: i64,
+    name: String,
+    // already an Option, converted as is
+    email: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct D {
+    id: Option<i64>,
+    name: Option<String>,
+    email: Option<String>,
+}
+
 fn main() {}
+
+#[test]
+fn test_wrap_option_both_directions() {
+    let a: A = B {
+        id: 1,
+        abc: 2,
+        def: 3,
+    }
+    .into();
+    assert_eq!((a.id, a.abc, a.def), (Some(1), Some(2), Some(3)));
+
+    let b: B = A {
+        id: Some(1),
+        abc: None,
+        def: Some(3),
+    }
+    .into();
+    debug_assert_eq!(
+        B {
+            id: 1,
+            abc: 0,
+            def: 3
+        },
+        b
+    );
+
+    let d: D = C {
+        id: 1,
+        name: "Jack".to_string(),
+        email: None,
+    }
+    .into();
+    debug_assert_eq!(
+        D {
+            id: Some(1),
+            name: Some("Jack".to_string()),
+            email: None,
+        },
+        d
+    );
+}
@@ -0,0 +1,73 @@
+#![allow(dead_code)]
+
+use just_convert::JustConvert;
+
+// Record has no Default and carries tenant_id which CreateRecord does not have
+#[derive(JustConvert)]
+#[convert(into(Record, base))]
+struct CreateRecord {
+    name: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    tenant_id: i64,
+    name: String,
+    version: u32,
+}
+
+// Fields skipped in `from` are taken from the base, `From<Record>` is generated as well
+#[derive(JustConvert, Debug, PartialEq)]
+#[convert(from(Record, with_base))]
+struct Entity {
+    name: String,
+    #[convert(skip)]
+    loaded_at: u64,
+}
+
+fn main() {}
+
+#[test]
+fn test_into_with_base() {
+    use just_convert::IntoWithBase;
+
+    let base = Record {
+        tenant_id: 42,
+        name: String::new(),
+        version: 1,
+    };
+    let record = CreateRecord {
+        name: "Jack".to_string(),
+    }
+    .into_with_base(base);
+    debug_assert_eq!(
+        Record {
+            tenant_id: 42,
+            name: "Jack".to_string(),
+            version: 1,
+        },
+        record
+    );
+}
+
+#[test]
+fn test_from_with_base() {
+    use just_convert::FromWithBase;
+
+    let base = Entity {
+        name: String::new(),
+        loaded_at: 100,
+    };
+    let record = Record {
+        tenant_id: 42,
+        name: "Jack".to_string(),
+        version: 1,
+    };
+    debug_assert_eq!(
+        Entity {
+            name: "Jack".to_string(),
+            loaded_at: 100,
+        },
+        Entity::from_with_base(record, base)
+    );
+}
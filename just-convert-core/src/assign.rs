@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{Result, Type};
+
+use crate::map::{field_uses, out_ident};
+
+/// Converted value of a target field
+pub(crate) struct Assign {
+    pub(crate) field: Ident,
+    /// `None` for the skipped field
+    pub(crate) value: Option<TokenStream>,
+    /// `skip_if` condition, when true the field is filled like a skipped one
+    pub(crate) skip_if: Option<TokenStream>,
+    /// Source field moved by the conversion, `None` for `map` and skipped fields
+    pub(crate) moves: Option<Ident>,
+    /// Span of the `map` or `map_if` expression, its moves are guessed from the tokens
+    pub(crate) map: Option<Span>,
+    /// Converted fields referenced as `out.field`, `true` if the value is moved
+    pub(crate) out_uses: Vec<(Ident, bool)>,
+    /// Type of the target field if known, used for the local holding the value
+    pub(crate) ty: Option<Type>,
+}
+
+impl Assign {
+    /// The value or `fallback` when the `skip_if` condition is true
+    pub(crate) fn value_or(&self, fallback: TokenStream) -> Option<TokenStream> {
+        let value = self.value.as_ref()?;
+        match &self.skip_if {
+            Some(cond) => Some(quote! { if #cond { #fallback } else { #value } }),
+            None => Some(value.clone()),
+        }
+    }
+
+    /// Source fields read by the value and the condition, `true` if the field is moved
+    fn uses(&self) -> Vec<(Ident, bool)> {
+        let mut uses = vec![];
+        if let Some(value) = &self.value {
+            let is_map = self.map.is_some();
+            let value_uses = field_uses(value.clone());
+            uses.extend(
+                value_uses
+                    .into_iter()
+                    .map(|(f, moved)| (f, moved && is_map)),
+            );
+        }
+        if let Some(cond) = &self.skip_if {
+            let cond_uses = field_uses(cond.clone());
+            uses.extend(cond_uses.into_iter().map(|(f, _)| (f, false)));
+        }
+        if let Some(moves) = &self.moves {
+            uses.push((moves.clone(), true));
+        }
+        uses
+    }
+
+    fn span(&self) -> Span {
+        self.map.unwrap_or_else(|| self.field.span())
+    }
+}
+
+/// Sort the assigns so that the source fields are read before they are moved
+/// and the values referenced as `out.field` are built before they are used,
+/// the struct literal fields are evaluated in the written order.
+/// Other assigns keep the declaration order, so the side effects of `with`
+/// or `ctx` calls are reordered only when such a dependency requires it.
+/// `types` are the known types of the source fields
+pub(crate) fn order_assigns(
+    assigns: Vec<Assign>,
+    types: &HashMap<Ident, Type>,
+) -> Result<Vec<Assign>> {
+    let uses = assigns.iter().map(Assign::uses).collect::<Vec<_>>();
+
+    check_double_moves(&assigns, &uses, types)?;
+
+    // `before[j]` are the assigns which must be evaluated before `j`
+    let mut before = out_dependencies(&assigns)?;
+    for (i, i_uses) in uses.iter().enumerate() {
+        for (j, j_uses) in uses.iter().enumerate() {
+            let reads_moved = i_uses
+                .iter()
+                .any(|(field, moved)| !moved && j_uses.iter().any(|(f, m)| *m && f == field));
+            if i != j && reads_moved {
+                before[j].push(i);
+            }
+        }
+    }
+
+    // the dependencies are pulled right before the first assign needing them
+    let mut order = vec![];
+    let mut visiting = vec![false; assigns.len()];
+    for j in 0..assigns.len() {
+        if let Err(i) = push_ordered(j, &mut before, &mut visiting, &mut order) {
+            return Err(syn::Error::new(
+                assigns[i].span(),
+                format!(
+                    "cannot order the conversion of field `{}`: it reads a source field moved by another field, which reads one moved by this one; clone one of them",
+                    assigns[i].field,
+                ),
+            ));
+        }
+    }
+
+    let mut assigns = assigns.into_iter().map(Some).collect::<Vec<_>>();
+    Ok(order
+        .into_iter()
+        .filter_map(|i| assigns[i].take())
+        .collect())
+}
+
+/// Push `j` after the assigns which must be evaluated before it,
+/// `Err` with an assign of a dependency cycle
+fn push_ordered(
+    j: usize,
+    before: &mut [Vec<usize>],
+    visiting: &mut [bool],
+    order: &mut Vec<usize>,
+) -> std::result::Result<(), usize> {
+    if order.contains(&j) {
+        return Ok(());
+    }
+    if visiting[j] {
+        return Err(j);
+    }
+    visiting[j] = true;
+    let mut dependencies = std::mem::take(&mut before[j]);
+    dependencies.sort_unstable();
+    dependencies.dedup();
+    for i in dependencies {
+        push_ordered(i, before, visiting, order)?;
+    }
+    order.push(j);
+    Ok(())
+}
+
+/// Assigns referenced by each one as `out.field`
+fn out_dependencies(assigns: &[Assign]) -> Result<Vec<Vec<usize>>> {
+    let mut dependencies = vec![];
+    for assign in assigns {
+        let mut assign_dependencies = vec![];
+        for (out, _) in &assign.out_uses {
+            let found = assigns.iter().position(|a| a.field == *out);
+            let Some(i) = found.filter(|&i| assigns[i].value.is_some()) else {
+                return Err(syn::Error::new(
+                    assign.span(),
+                    format!("`out.{out}` is not a converted field of the target"),
+                ));
+            };
+            if assigns[i].skip_if.is_some() {
+                return Err(syn::Error::new(
+                    assign.span(),
+                    format!("`out.{out}` cannot refer to a field with `skip_if`"),
+                ));
+            }
+            assign_dependencies.push(i);
+        }
+        dependencies.push(assign_dependencies);
+    }
+
+    for start in 0..assigns.len() {
+        let mut visited = vec![false; assigns.len()];
+        if let Some(cycle) = find_cycle(&dependencies, vec![start], &mut visited) {
+            let names = cycle
+                .iter()
+                .map(|&i| format!("`{}`", assigns[i].field))
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            return Err(syn::Error::new(
+                assigns[start].span(),
+                format!("cyclic `out` references: {names}"),
+            ));
+        }
+    }
+
+    Ok(dependencies)
+}
+
+/// Path of the dependencies back to the first element of `path`
+fn find_cycle(
+    dependencies: &[Vec<usize>],
+    path: Vec<usize>,
+    visited: &mut [bool],
+) -> Option<Vec<usize>> {
+    let last = *path.last()?;
+    for &next in &dependencies[last] {
+        if next == path[0] {
+            let mut cycle = path.clone();
+            cycle.push(next);
+            return Some(cycle);
+        }
+        if visited[next] {
+            continue;
+        }
+        visited[next] = true;
+        let mut longer = path.clone();
+        longer.push(next);
+        if let Some(cycle) = find_cycle(dependencies, longer, visited) {
+            return Some(cycle);
+        }
+    }
+    None
+}
+
+/// Source fields read by the values, in order of the first use
+pub(crate) fn source_fields(assigns: &[Assign]) -> Vec<Ident> {
+    let mut fields: Vec<Ident> = vec![];
+    for (field, _) in assigns.iter().flat_map(Assign::uses) {
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+    fields
+}
+
+/// The values must be bound to locals, because some of them refer to others as `out.field`
+pub(crate) fn needs_binding(assigns: &[Assign]) -> bool {
+    assigns.iter().any(|assign| !assign.out_uses.is_empty())
+}
+
+/// Struct literal of the values in the evaluation order,
+/// with `bind` the values are bound to `out_field` locals first
+pub(crate) fn build_literal(
+    path: &impl ToTokens,
+    values: Vec<(&Assign, TokenStream)>,
+    bind: bool,
+    rest: TokenStream,
+) -> TokenStream {
+    let fields = values
+        .iter()
+        .map(|(assign, _)| &assign.field)
+        .collect::<Vec<_>>();
+    if !bind {
+        let values = values.iter().map(|(_, value)| value);
+        return quote! {
+            #path {
+                #(#fields: #values,)*
+                #rest
+            }
+        };
+    }
+
+    let locals = fields
+        .iter()
+        .map(|field| out_ident(field))
+        .collect::<Vec<_>>();
+    let binds = values.iter().zip(&locals).map(|((assign, value), local)| {
+        let ty = assign.ty.as_ref().map(|ty| quote!(: #ty));
+        quote! { let #local #ty = #value; }
+    });
+    quote! {{
+        #(#binds)*
+        #path {
+            #(#fields: #locals,)*
+            #rest
+        }
+    }}
+}
+
+/// A source field of a type which is surely not `Copy` can be moved only once,
+/// the same for a converted value, which is always moved into the target
+fn check_double_moves(
+    assigns: &[Assign],
+    uses: &[Vec<(Ident, bool)>],
+    types: &HashMap<Ident, Type>,
+) -> Result<()> {
+    for assign in assigns {
+        for (out, _) in assign.out_uses.iter().filter(|(_, moved)| *moved) {
+            let target = assigns.iter().find(|a| a.field == *out);
+            if target.is_some_and(|target| target.ty.as_ref().is_some_and(is_not_copy)) {
+                return Err(syn::Error::new(
+                    assign.span(),
+                    format!(
+                        "`map` of field `{}` moves `out.{out}`, which is also moved into field `{out}`; borrow it with `&out.{out}` or clone it",
+                        assign.field,
+                    ),
+                ));
+            }
+        }
+    }
+
+    for (i, assign) in assigns.iter().enumerate() {
+        if assign.map.is_none() {
+            continue;
+        }
+        for (field, _) in uses[i].iter().filter(|(_, moved)| *moved) {
+            if !types.get(field).is_some_and(is_not_copy) {
+                continue;
+            }
+            let other = uses.iter().enumerate().find(|(j, j_uses)| {
+                *j != i && j_uses.iter().any(|(f, moved)| *moved && f == field)
+            });
+            if let Some((j, _)) = other {
+                return Err(syn::Error::new(
+                    assign.span(),
+                    format!(
+                        "`map` of field `{}` moves `this.{field}`, which is also moved by field `{}`; borrow it with `&this.{field}` or clone it",
+                        assign.field, assigns[j].field,
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Standard types which are never `Copy`
+fn is_not_copy(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    let Some(segment) = type_path.path.segments.last() else {
+        return false;
+    };
+
+    const NOT_COPY: &[&str] = &[
+        "String", "Vec", "Box", "HashMap", "HashSet", "BTreeMap", "BTreeSet", "VecDeque", "Rc",
+        "Arc",
+    ];
+    if NOT_COPY.iter().any(|name| segment.ident == name) {
+        return true;
+    }
+
+    // `Option<T>` is `Copy` only if `T` is
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    segment.ident == "Option"
+        && args.args.iter().any(|arg| match arg {
+            syn::GenericArgument::Type(ty) => is_not_copy(ty),
+            _ => false,
+        })
+}
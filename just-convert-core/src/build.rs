@@ -0,0 +1,1092 @@
+use std::collections::HashMap;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{parse_quote, Path, Result, Type};
+
+use crate::{
+    assign::{build_literal, needs_binding, order_assigns, Assign},
+    map::{
+        bind_out_refs, field_uses, out_field_uses, out_ident, parse_map_expr, parse_map_expr_with,
+    },
+    path_to_string, AdditionalType, Conversion, FieldParams, FieldValue, Fields, Generate, MapIf,
+    Params, PathParams, Unwrap,
+};
+
+impl<T: Clone> FieldValue<T> {
+    fn get_from(&self, path: &Path) -> Option<T> {
+        self.from
+            .get(path)
+            .cloned()
+            .or_else(|| self.common_from.clone())
+            .or_else(|| self.common.clone())
+    }
+
+    fn get_into(&self, path: &Path) -> Option<T> {
+        self.into
+            .get(path)
+            .cloned()
+            .or_else(|| self.common_into.clone())
+            .or_else(|| self.common.clone())
+    }
+
+    /// Only the value written for this path, e.g. `map(from(Path, ".."))`
+    fn get_from_path(&self, path: &Path) -> Option<T> {
+        self.from.get(path).cloned()
+    }
+}
+
+impl FieldParams {
+    /// Field is converted without `skip`, `map`, `with` or field-level wrap/unwrap
+    fn is_plain_from(&self, path: &Path) -> bool {
+        !self.skip.get_from(path).unwrap_or_default()
+            && self.map.get_from(path).is_none()
+            && self.map_if.get_from(path).is_none()
+            && self.with.get_from(path).is_none()
+            && self.await_with.get_from(path).is_none()
+            && !self.none_variant.get_from(path).unwrap_or_default()
+            && !self.wrap.get_from(path).unwrap_or_default()
+            && self.unwrap.get_from(path).is_none()
+            && self.conversion_from(path).is_none()
+    }
+
+    /// Field is converted without `skip`, `map`, `with` or field-level wrap/unwrap
+    fn is_plain_into(&self, path: &Path) -> bool {
+        !self.skip.get_into(path).unwrap_or_default()
+            && self.map.get_into(path).is_none()
+            && self.map_if.get_into(path).is_none()
+            && self.with.get_into(path).is_none()
+            && self.await_with.get_into(path).is_none()
+            && !self.none_variant.get_into(path).unwrap_or_default()
+            && !self.wrap.get_into(path).unwrap_or_default()
+            && self.unwrap.get_into(path).is_none()
+            && self.conversion_into(path).is_none()
+    }
+
+    /// `cast`, `display` or another value conversion used for `from(path)`,
+    /// the most specific one wins like for the other options
+    fn conversion_from(&self, path: &Path) -> Option<Conversion> {
+        [
+            (Conversion::Cast, &self.cast),
+            (Conversion::Saturating, &self.saturating),
+            (Conversion::Wrapping, &self.wrapping),
+            (Conversion::Checked, &self.checked),
+            (Conversion::Display, &self.display),
+            (Conversion::Parse, &self.parse),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.get_from(path).unwrap_or_default())
+        .min_by_key(|(_, value)| value.level_from(path))
+        .map(|(numeric, _)| numeric)
+    }
+
+    /// `cast`, `display` or another value conversion used for `into(path)`,
+    /// the most specific one wins like for the other options
+    fn conversion_into(&self, path: &Path) -> Option<Conversion> {
+        [
+            (Conversion::Cast, &self.cast),
+            (Conversion::Saturating, &self.saturating),
+            (Conversion::Wrapping, &self.wrapping),
+            (Conversion::Checked, &self.checked),
+            (Conversion::Display, &self.display),
+            (Conversion::Parse, &self.parse),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.get_into(path).unwrap_or_default())
+        .min_by_key(|(_, value)| value.level_into(path))
+        .map(|(numeric, _)| numeric)
+    }
+}
+
+impl Params {
+    pub(crate) fn build(mut self) -> Result<TokenStream> {
+        if let Some(variants) = &self.variants {
+            return self.build_enum(variants);
+        }
+
+        let generate_items = self.build_generate();
+        let patch_items = self.build_patch()?;
+        let from_impl = self.build_from()?;
+        let into_impl = self.build_into()?;
+
+        Ok(quote! {
+            #generate_items
+            #patch_items
+            #from_impl
+            #into_impl
+        })
+    }
+
+    fn build_generate(&self) -> TokenStream {
+        let mut items = vec![];
+
+        for generate in &self.generate {
+            let path = Path::from(generate.name.clone());
+
+            let fields = self
+                .fields
+                .iter()
+                .filter(|(_, params)| !params.skip.get_into(&path).unwrap_or_default())
+                .map(|(field, params)| {
+                    let field = params
+                        .rename
+                        .get_into(&path)
+                        .unwrap_or_else(|| field.clone());
+                    let ty = params
+                        .ty
+                        .get_into(&path)
+                        .unwrap_or_else(|| params.field_ty.clone());
+                    (field, ty)
+                });
+
+            items.push(build_struct(generate, fields));
+        }
+
+        quote! {
+            #(#items)*
+        }
+    }
+
+    /// Struct with all fields optional and `apply_patch` method
+    /// which overwrites only fields set in the patch
+    fn build_patch(&self) -> Result<TokenStream> {
+        let Some(patch) = &self.patch else {
+            return Ok(quote!());
+        };
+        let path = Path::from(patch.name.clone());
+
+        let mut fields = vec![];
+        let mut applies = vec![];
+        for (field, params) in &self.fields {
+            if params.skip.get_from(&path).unwrap_or_default() {
+                continue;
+            }
+
+            let patch_field = params
+                .rename
+                .get_from(&path)
+                .unwrap_or_else(|| field.clone());
+            let ty = params
+                .ty
+                .get_from(&path)
+                .unwrap_or_else(|| params.field_ty.clone());
+            fields.push((patch_field.clone(), parse_quote!(Option<#ty>)));
+
+            // `.` is the value set in the patch, `map` and `with` of other conversions
+            // expect the values of other types, so only the ones written for the patch apply
+            let map = params.map.get_from_path(&path);
+            let value = match (map, params.with.get_from_path(&path)) {
+                (Some(map), _) => {
+                    let map_expr = parse_map_expr_with(quote!(value), map)?;
+                    quote!(#map_expr)
+                }
+                (None, Some(with)) => quote!(#with(value)),
+                (None, None) => quote!(value.into()),
+            };
+
+            applies.push(quote! {
+                if let Some(value) = this.#patch_field {
+                    self.#field = #value;
+                }
+            });
+        }
+
+        let patch_struct = build_struct(patch, fields);
+        let current = &self.name;
+        let Generate { name, vis, .. } = patch;
+
+        Ok(quote! {
+            #patch_struct
+
+            impl #current {
+                #vis fn apply_patch(&mut self, patch: #name) {
+                    let this = patch;
+                    #(#applies)*
+                }
+            }
+        })
+    }
+
+    fn build_from(&mut self) -> Result<TokenStream> {
+        let mut items = vec![];
+
+        for from in &self.from {
+            if let Some(shape) = from.variant {
+                items.push(self.build_from_variant(from, shape)?);
+                continue;
+            }
+
+            let current = self.name.clone();
+            let from_path = &from.path;
+            let krate = &from.krate;
+            let values = build_from_assigns(from, &current, self.fields.clone())?;
+
+            let bind = needs_binding(&values);
+            let assigns = values
+                .iter()
+                .filter_map(|assign| match assign.value_or(quote!(Default::default())) {
+                    Some(value) => Some((assign, value)),
+                    None if from.default => None,
+                    None => Some((assign, quote!(Default::default()))),
+                })
+                .collect();
+
+            let default_expr = if from.default {
+                quote! { ..Default::default() }
+            } else {
+                quote!()
+            };
+
+            let body = build_value(from, build_literal(&current, assigns, bind, default_expr));
+
+            let item = if let Some(context) = &from.context {
+                quote! {
+                    impl #krate::FromWithContext<#from_path, #context> for #current {
+                        #[allow(unused_variables)]
+                        fn from_with_context(this: #from_path, ctx: &#context) -> Self {
+                            #body
+                        }
+                    }
+                }
+            } else if from.is_async {
+                quote! {
+                    impl #krate::AsyncFrom<#from_path> for #current {
+                        fn async_from(
+                            this: #from_path,
+                        ) -> impl std::future::Future<Output = Self> + Send {
+                            async move { #body }
+                        }
+                    }
+                }
+            } else if from.fallible {
+                quote! {
+                    impl std::convert::TryFrom<#from_path> for #current {
+                        type Error = Box<dyn std::error::Error + Send + Sync>;
+
+                        fn try_from(this: #from_path) -> Result<Self, Self::Error> {
+                            #body
+                        }
+                    }
+                }
+            } else {
+                let updates = build_updates(&values, bind);
+                let base_assigns = values
+                    .iter()
+                    .filter_map(|assign| {
+                        let field = &assign.field;
+                        let value = assign.value_or(quote!(base.#field))?;
+                        Some((assign, value))
+                    })
+                    .collect();
+                let after = from.after.iter().map(|after| quote! { #after(self); });
+                let base_body = build_value(
+                    from,
+                    build_literal(&current, base_assigns, bind, quote!(..base)),
+                );
+
+                // with `base` the unmapped fields could be impossible to fill
+                let from_impl = if from.base.is_some() {
+                    quote!()
+                } else {
+                    quote! {
+                        impl std::convert::From<#from_path> for #current {
+                            fn from(this: #from_path) -> Self {
+                                #body
+                            }
+                        }
+                    }
+                };
+
+                let base_impl = if from.base.is_some() || from.with_base.is_some() {
+                    quote! {
+                        impl #krate::FromWithBase<#from_path> for #current {
+                            fn from_with_base(this: #from_path, base: Self) -> Self {
+                                #base_body
+                            }
+                        }
+                    }
+                } else {
+                    quote!()
+                };
+
+                quote! {
+                    #from_impl
+
+                    impl #krate::UpdateFrom<#from_path> for #current {
+                        fn update_from(&mut self, src: #from_path) {
+                            let this = src;
+                            #updates
+                            #(#after)*
+                        }
+                    }
+
+                    #base_impl
+                }
+            };
+            items.push(item);
+        }
+
+        Ok(quote! {
+            #(#items)*
+        })
+    }
+
+    fn build_into(&mut self) -> Result<TokenStream> {
+        let mut items = vec![];
+
+        for into in &self.into {
+            if let Some(shape) = into.variant {
+                items.push(self.build_into_variant(into, shape)?);
+                continue;
+            }
+
+            let current = self.name.clone();
+            let into_path = &into.path;
+            let krate = &into.krate;
+            let values = build_into_assigns(into, &current, self.fields.clone())?;
+            let bind = needs_binding(&values);
+            let assigns = values
+                .iter()
+                .filter_map(|assign| {
+                    let value = assign.value_or(quote!(Default::default()))?;
+                    Some((assign, value))
+                })
+                .collect();
+
+            let default_expr = if into.default {
+                quote! { ..Default::default() }
+            } else {
+                quote!()
+            };
+
+            let body = build_value(into, build_literal(into_path, assigns, bind, default_expr));
+
+            let item = if let Some(context) = &into.context {
+                quote! {
+                    impl #krate::IntoWithContext<#into_path, #context> for #current {
+                        #[allow(unused_variables)]
+                        fn into_with_context(self, ctx: &#context) -> #into_path {
+                            let this = self;
+                            #body
+                        }
+                    }
+                }
+            } else if into.fallible {
+                quote! {
+                    impl std::convert::TryInto<#into_path> for #current {
+                        type Error = Box<dyn std::error::Error + Send + Sync>;
+
+                        fn try_into(self) -> Result<#into_path, Self::Error> {
+                            let this = self;
+                            #body
+                        }
+                    }
+                }
+            } else {
+                let base_assigns = values
+                    .iter()
+                    .filter_map(|assign| {
+                        let field = &assign.field;
+                        let value = assign.value_or(quote!(base.#field))?;
+                        Some((assign, value))
+                    })
+                    .collect();
+                let base_body = build_value(
+                    into,
+                    build_literal(into_path, base_assigns, bind, quote!(..base)),
+                );
+
+                // with `base` the unmapped fields could be impossible to fill
+                let into_impl = if into.base.is_some() {
+                    quote!()
+                } else {
+                    quote! {
+                        impl std::convert::Into<#into_path> for #current {
+                            fn into(self) -> #into_path {
+                                let this = self;
+                                #body
+                            }
+                        }
+                    }
+                };
+
+                let base_impl = if into.base.is_some() || into.with_base.is_some() {
+                    quote! {
+                        impl #krate::IntoWithBase<#into_path> for #current {
+                            fn into_with_base(self, base: #into_path) -> #into_path {
+                                let this = self;
+                                #base_body
+                            }
+                        }
+                    }
+                } else {
+                    quote!()
+                };
+
+                quote! {
+                    #into_impl
+                    #base_impl
+                }
+            };
+            items.push(item);
+        }
+
+        Ok(quote! {
+            #(#items)*
+        })
+    }
+}
+
+/// Statements of `update_from`, with `bind` the values are bound to locals first
+fn build_updates(values: &[Assign], bind: bool) -> TokenStream {
+    let mut updates = vec![];
+    let mut binds = vec![];
+    for Assign {
+        field,
+        value,
+        skip_if,
+        ty,
+        ..
+    } in values
+    {
+        let Some(value) = value else {
+            continue;
+        };
+        match skip_if {
+            Some(cond) => updates.push(quote! { if !(#cond) { self.#field = #value; } }),
+            None if bind => {
+                let local = out_ident(field);
+                let ty = ty.as_ref().map(|ty| quote!(: #ty));
+                binds.push(quote! { let #local #ty = #value; });
+                updates.push(quote! { self.#field = #local; });
+            }
+            None => updates.push(quote! { self.#field = #value; }),
+        }
+    }
+
+    quote! {
+        #(#binds)*
+        #(#updates)*
+    }
+}
+
+/// Statements of the conversion body, the built value
+/// is passed to the `after` and `validate` hooks
+pub(crate) fn build_value(target: &PathParams, value: TokenStream) -> TokenStream {
+    let PathParams {
+        fallible,
+        after,
+        validate,
+        ..
+    } = target;
+
+    if after.is_none() && validate.is_none() {
+        let value = if *fallible { quote!(Ok(#value)) } else { value };
+        return quote! {
+            #[allow(clippy::needless_update)]
+            #value
+        };
+    }
+
+    let mutability = after.as_ref().map(|_| quote!(mut));
+    let after = after.iter().map(|after| quote! { #after(&mut value); });
+    let validate = validate
+        .iter()
+        .map(|validate| quote! { #validate(&value)?; });
+    let result = if *fallible {
+        quote!(Ok(value))
+    } else {
+        quote!(value)
+    };
+
+    quote! {
+        #[allow(clippy::needless_update)]
+        let #mutability value = #value;
+        #(#after)*
+        #(#validate)*
+        #result
+    }
+}
+
+/// Declaration of the generated struct
+fn build_struct(
+    Generate { name, derives, vis }: &Generate,
+    fields: impl IntoIterator<Item = (Ident, Type)>,
+) -> TokenStream {
+    let fields = fields
+        .into_iter()
+        .map(|(field, ty)| quote! { #vis #field: #ty, });
+
+    let derive = if derives.is_empty() {
+        quote!()
+    } else {
+        quote! { #[derive(#(#derives),*)] }
+    };
+
+    quote! {
+        #derive
+        #vis struct #name {
+            #(#fields)*
+        }
+    }
+}
+
+/// Value of each field of the annotated struct
+pub(crate) fn build_from_assigns(
+    target: &PathParams,
+    current: &Ident,
+    fields: Fields,
+) -> Result<Vec<Assign>> {
+    check_container_switches(target, &fields, |params| {
+        let wrap = target.wrap_option.is_some() && params.a_type.is_option();
+        let unwrap = target.unwrap_option.is_some() && params.a_type.is_none();
+        (wrap || unwrap) && params.is_plain_from(&target.path)
+    })?;
+
+    let mut items = vec![];
+    let mut types = HashMap::new();
+    for (field, params) in fields {
+        let path = &target.path;
+        let skip_if = match params.skip_if.get_from(path) {
+            Some(cond) => Some(parse_map_expr(field.clone(), cond)?.to_token_stream()),
+            None => None,
+        };
+        let map = (params.map.get_from(path))
+            .or_else(|| params.map_if.get_from(path).map(|map_if| map_if.cond))
+            .map(|lit| lit.span());
+        let source = params
+            .rename
+            .get_from(path)
+            .unwrap_or_else(|| field.clone());
+        if let Some(source_ty) = other_field_ty(&params, target, params.ty.get_from(path)) {
+            types.insert(source.clone(), source_ty);
+        }
+        let ty = Some(params.field_ty.clone());
+
+        let value = build_from_assign_item(field.clone(), params, target, current)?;
+        let moves = value.as_ref().and(map.is_none().then_some(source));
+        items.push(Assign {
+            field,
+            value,
+            skip_if,
+            moves,
+            map,
+            out_uses: vec![],
+            ty,
+        });
+    }
+    bind_assign_out_refs(&mut items);
+    order_assigns(items, &types)
+}
+
+/// Type of the field in the converted struct when it is known:
+/// the field type of the generated struct or the `declared` one with `ty`
+fn other_field_ty(
+    params: &FieldParams,
+    target: &PathParams,
+    declared: Option<Type>,
+) -> Option<Type> {
+    if target.generated {
+        let ty = params.ty.get_into(&target.path);
+        return Some(ty.unwrap_or_else(|| params.field_ty.clone()));
+    }
+    declared
+}
+
+/// Replace `out.field` of the target fields in the values and the `skip_if` conditions
+/// with the locals, the conditions never move the values
+fn bind_assign_out_refs(assigns: &mut [Assign]) {
+    let fields = assigns.iter().map(|a| a.field.clone()).collect::<Vec<_>>();
+    for assign in assigns {
+        let mut out_uses = vec![];
+        let mut bind = |tokens: TokenStream, can_move: bool| {
+            let uses = out_field_uses(tokens.clone()).into_iter();
+            let uses = uses.filter(|(field, _)| fields.contains(field));
+            out_uses.extend(uses.map(|(field, moved)| (field, moved && can_move)));
+            bind_out_refs(tokens, &fields)
+        };
+        assign.value = assign.value.take().map(|value| bind(value, true));
+        assign.skip_if = assign.skip_if.take().map(|skip_if| bind(skip_if, false));
+        assign.out_uses = out_uses;
+    }
+}
+
+/// Container switches like `wrap_option` must change at least one field,
+/// otherwise they most likely were declared for the wrong direction
+fn check_container_switches(
+    target: &PathParams,
+    fields: &Fields,
+    is_affected: impl Fn(&FieldParams) -> bool,
+) -> Result<()> {
+    let switch = target
+        .wrap_option
+        .as_ref()
+        .or(target.unwrap_option.as_ref());
+    let Some(switch) = switch else {
+        return Ok(());
+    };
+
+    if fields.iter().any(|(_, params)| is_affected(params)) {
+        return Ok(());
+    }
+
+    Err(syn::Error::new(
+        switch.span(),
+        format!("`{switch}` has no effect on any field for this conversion direction"),
+    ))
+}
+
+fn build_from_assign_item(
+    left_field: Ident,
+    params: FieldParams,
+    PathParams {
+        path: target,
+        fallible,
+        is_async,
+        wrap_option,
+        unwrap_option,
+        context,
+        krate,
+        ..
+    }: &PathParams,
+    current: &Ident,
+) -> syn::Result<Option<TokenStream>> {
+    if params.skip.get_from(target).unwrap_or_default() {
+        return Ok(None);
+    }
+
+    if let Some(map) = params.map.get_from(target) {
+        let map_expr = parse_map_expr(left_field.clone(), map)?;
+        return Ok(Some(quote! { #map_expr }));
+    }
+
+    if let Some(map_if) = params.map_if.get_from(target) {
+        return build_map_if_expr(&left_field, map_if).map(Some);
+    }
+
+    let right_field = match params.rename.get_from(target) {
+        Some(n) => n,
+        None => left_field.clone(),
+    };
+
+    if let Some(await_with) = params.await_with.get_from(target) {
+        if !is_async {
+            return Err(syn::Error::new(
+                left_field.span(),
+                format!(
+                    "`await_with` requires an async conversion, use `async_from({})`",
+                    path_to_string(target),
+                ),
+            ));
+        }
+        return Ok(Some(quote! { #await_with(this.#right_field).await }));
+    }
+
+    if let Some(with) = params.with.get_from(target) {
+        let ctx = context.as_ref().map(|_| quote! { , ctx });
+        return Ok(Some(quote! { #with(this.#right_field #ctx) }));
+    }
+
+    if let Some(conversion) = params.conversion_from(target) {
+        let message = format!(
+            "can not parse field `{{}}` converting {} -> {current}: {{}}",
+            path_to_string(target),
+        );
+        return build_conversion_expr(&right_field, conversion, &params, *fallible, krate, message)
+            .map(Some);
+    }
+
+    if let Some(unwrap) = params.unwrap.get_from(target) {
+        let message = format!(
+            "field `{right_field}` missing converting {} -> {current}",
+            path_to_string(target),
+        );
+        let unwrap_expr = build_unwrap_expr(&right_field, unwrap, *fallible, message)?;
+        return Ok(Some(quote! { #unwrap_expr }));
+    }
+
+    if params.none_variant.get_from(target).unwrap_or_default() {
+        return build_none_variant_expr(&left_field, &right_field, &params).map(Some);
+    }
+
+    if params.wrap.get_from(target).unwrap_or_default() && !params.a_type.is_option() {
+        return Err(syn::Error::new(
+            left_field.span(),
+            "`wrap` requires an `Option` field when converting from",
+        ));
+    }
+
+    if params.a_type.is_option()
+        && (params.wrap.get_from(target).unwrap_or_default() || wrap_option.is_some())
+    {
+        return Ok(Some(quote! { Some(this.#right_field) }));
+    }
+
+    if params.a_type.is_none() && unwrap_option.is_some() {
+        return Ok(Some(quote! { this.#right_field.unwrap_or_default() }));
+    }
+
+    if params.a_type.is_option() {
+        return Ok(Some(quote! { this.#right_field.map(Into::into) }));
+    }
+
+    if params.a_type.is_option_vec() {
+        return Ok(Some(
+            quote! { this.#right_field.map(|x| x.into_iter().map(Into::into).collect()) },
+        ));
+    }
+
+    if params.a_type.is_vec() {
+        return Ok(Some(
+            quote! { this.#right_field.into_iter().map(Into::into).collect() },
+        ));
+    }
+
+    if params.a_type.is_vec_option() {
+        return Ok(Some(
+            quote! { this.#right_field.into_iter().map(|x| x.map(Into::into)).collect() },
+        ));
+    }
+
+    Ok(Some(quote! { this.#right_field.into() }))
+}
+
+/// Value of each field of the target struct, skipped ones are omitted
+pub(crate) fn build_into_assigns(
+    target: &PathParams,
+    current: &Ident,
+    fields: Fields,
+) -> Result<Vec<Assign>> {
+    check_container_switches(target, &fields, |params| {
+        let wrap = target.wrap_option.is_some() && params.a_type.is_none();
+        let unwrap = target.unwrap_option.is_some() && params.a_type.is_option();
+        (wrap || unwrap) && params.is_plain_into(&target.path)
+    })?;
+
+    let mut items = vec![];
+    let types = (fields.iter())
+        .map(|(field, params)| (field.clone(), params.field_ty.clone()))
+        .collect();
+    for (field, params) in fields {
+        let path = &target.path;
+        let skip_if = match params.skip_if.get_into(path) {
+            Some(cond) => Some(parse_map_expr(field.clone(), cond)?.to_token_stream()),
+            None => None,
+        };
+        let map = (params.map.get_into(path))
+            .or_else(|| params.map_if.get_into(path).map(|map_if| map_if.cond))
+            .map(|lit| lit.span());
+        let source = field.clone();
+        let ty = other_field_ty(&params, target, params.ty.get_into(path));
+
+        let Some((field, value)) = build_into_assign_item(field, params, target, current)? else {
+            continue;
+        };
+        items.push(Assign {
+            field,
+            value: Some(value),
+            skip_if,
+            moves: map.is_none().then_some(source),
+            map,
+            out_uses: vec![],
+            ty,
+        });
+    }
+    bind_assign_out_refs(&mut items);
+    order_assigns(items, &types)
+}
+
+fn build_into_assign_item(
+    right_field: Ident,
+    params: FieldParams,
+    PathParams {
+        path: target,
+        fallible,
+        wrap_option,
+        unwrap_option,
+        context,
+        krate,
+        ..
+    }: &PathParams,
+    current: &Ident,
+) -> syn::Result<Option<(Ident, TokenStream)>> {
+    if params.skip.get_into(target).unwrap_or_default() {
+        return Ok(None);
+    }
+
+    let left_field = match params.rename.get_into(target) {
+        Some(n) => n,
+        None => right_field.clone(),
+    };
+
+    if let Some(map) = params.map.get_into(target) {
+        let map_expr = parse_map_expr(right_field, map)?;
+        return Ok(Some((left_field, quote! { #map_expr })));
+    }
+
+    if let Some(map_if) = params.map_if.get_into(target) {
+        let map_if_expr = build_map_if_expr(&right_field, map_if)?;
+        return Ok(Some((left_field, map_if_expr)));
+    }
+
+    if params.await_with.get_into(target).is_some() {
+        return Err(syn::Error::new(
+            right_field.span(),
+            "`await_with` is not supported for `into` conversions",
+        ));
+    }
+
+    if let Some(with) = params.with.get_into(target) {
+        let ctx = context.as_ref().map(|_| quote! { , ctx });
+        return Ok(Some((left_field, quote! { #with(this.#right_field #ctx) })));
+    }
+
+    if let Some(conversion) = params.conversion_into(target) {
+        let message = format!(
+            "can not parse field `{{}}` converting {current} -> {}: {{}}",
+            path_to_string(target),
+        );
+        let conversion_expr =
+            build_conversion_expr(&right_field, conversion, &params, *fallible, krate, message)?;
+        return Ok(Some((left_field, conversion_expr)));
+    }
+
+    if let Some(unwrap) = params.unwrap.get_into(target) {
+        let message = format!(
+            "field `{right_field}` missing converting {current} -> {}",
+            path_to_string(target),
+        );
+        let unwrap_expr = build_unwrap_expr(&right_field, unwrap, *fallible, message)?;
+        return Ok(Some((left_field, quote! { #unwrap_expr })));
+    }
+
+    if params.none_variant.get_into(target).unwrap_or_default() {
+        let value = build_none_variant_expr(&right_field, &right_field, &params)?;
+        return Ok(Some((left_field, value)));
+    }
+
+    if params.wrap.get_into(target).unwrap_or_default() && !params.a_type.is_none() {
+        return Err(syn::Error::new(
+            right_field.span(),
+            "`wrap` requires a non-`Option` field when converting into",
+        ));
+    }
+
+    if params.a_type.is_none()
+        && (params.wrap.get_into(target).unwrap_or_default() || wrap_option.is_some())
+    {
+        return Ok(Some((left_field, quote! { Some(this.#right_field) })));
+    }
+
+    if params.a_type.is_option() && unwrap_option.is_some() {
+        return Ok(Some((
+            left_field,
+            quote! { this.#right_field.unwrap_or_default() },
+        )));
+    }
+
+    if params.a_type.is_option() {
+        return Ok(Some((
+            left_field,
+            quote! { this.#right_field.map(Into::into) },
+        )));
+    }
+
+    if params.a_type.is_option_vec() {
+        return Ok(Some((
+            left_field,
+            quote! { this.#right_field.map(|x| x.into_iter().map(Into::into).collect()) },
+        )));
+    }
+
+    if params.a_type.is_vec() {
+        return Ok(Some((
+            left_field,
+            quote! { this.#right_field.into_iter().map(Into::into).collect() },
+        )));
+    }
+
+    if params.a_type.is_vec_option() {
+        return Ok(Some((
+            left_field,
+            quote! { this.#right_field.into_iter().map(|x| x.map(Into::into)).collect() },
+        )));
+    }
+
+    Ok(Some((left_field, quote! { this.#right_field.into() })))
+}
+
+/// Every `Option` of the field is converted as a whole with the `none` variant of the enum
+fn build_none_variant_expr(
+    field: &Ident,
+    source: &Ident,
+    params: &FieldParams,
+) -> Result<TokenStream> {
+    let a_type = params.a_type;
+    if a_type.is_option() {
+        return Ok(quote! { this.#source.into() });
+    }
+    if a_type.is_vec_option() {
+        return Ok(quote! { this.#source.into_iter().map(Into::into).collect() });
+    }
+    // the elements of `Option<Vec<T>>` have to be options too
+    let element = type_argument(&params.field_ty).and_then(type_argument);
+    let is_option = |ty: &Type| matches!(ty, Type::Path(ty) if ty.path.segments.last().is_some_and(|s| s.ident == "Option"));
+    if a_type.is_option_vec() && element.is_some_and(is_option) {
+        return Ok(quote! { this.#source.map(|x| x.into_iter().map(Into::into).collect()) });
+    }
+    Err(syn::Error::new(
+        field.span(),
+        "`none_variant` requires an `Option<_>`, `Vec<Option<_>>` or `Option<Vec<Option<_>>>` field",
+    ))
+}
+
+/// The first type argument of `Outer<T>`
+fn type_argument(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &type_path.path.segments.last()?.arguments
+    else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Value of the field converted with `cast`, `display`, `parse` and others
+fn build_conversion_expr(
+    field: &Ident,
+    conversion: Conversion,
+    params: &FieldParams,
+    fallible: bool,
+    krate: &Path,
+    parse_message: String,
+) -> Result<TokenStream> {
+    let a_type = params.a_type;
+    match conversion {
+        Conversion::Checked | Conversion::Parse if !fallible => Err(syn::Error::new(
+            field.span(),
+            format!(
+                "`{}` requires a fallible conversion, use `try_from` or `try_into`",
+                conversion.name(),
+            ),
+        )),
+        Conversion::Checked => Ok(build_nested_try_map(field, a_type, |x, path| {
+            quote! { #krate::NumConvert::checked(#x).map_err(|err| err.with_field(#path)) }
+        })),
+        Conversion::Parse => Ok(build_nested_try_map(field, a_type, |x, path| {
+            quote! { #x.parse().map_err(|err| format!(#parse_message, #path, err)) }
+        })),
+        Conversion::Display => Ok(build_nested_map(
+            field,
+            a_type,
+            |x| quote! { #x.to_string() },
+        )),
+        Conversion::Cast | Conversion::Saturating | Conversion::Wrapping => {
+            let method = format_ident!("{}", conversion.name());
+            Ok(build_nested_map(field, a_type, |x| {
+                quote! { #krate::NumConvert::#method(#x) }
+            }))
+        }
+    }
+}
+
+/// `convert` applied to the value, or to each value inside `Option`/`Vec`
+fn build_nested_map(
+    field: &Ident,
+    a_type: AdditionalType,
+    convert: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let item = convert(quote!(x));
+    match a_type {
+        AdditionalType::None => convert(quote! { this.#field }),
+        AdditionalType::Option => quote! { this.#field.map(|x| #item) },
+        AdditionalType::OptionVec => {
+            quote! { this.#field.map(|x| x.into_iter().map(|x| #item).collect()) }
+        }
+        AdditionalType::Vec => quote! { this.#field.into_iter().map(|x| #item).collect() },
+        AdditionalType::VecOption => {
+            quote! { this.#field.into_iter().map(|x| x.map(|x| #item)).collect() }
+        }
+    }
+}
+
+/// Same as [`build_nested_map`] for a `convert` returning `Result`,
+/// it also gets the field path of the value for the error, e.g. `scores[2]`
+fn build_nested_try_map(
+    field: &Ident,
+    a_type: AdditionalType,
+    convert: impl Fn(TokenStream, TokenStream) -> TokenStream,
+) -> TokenStream {
+    let name = field.to_string();
+    let index = format!("{name}[{{}}]");
+    let item = convert(quote!(x), quote!(#name));
+    let indexed = convert(quote!(x), quote!(format!(#index, i)));
+    match a_type {
+        AdditionalType::None => {
+            let value = convert(quote! { this.#field }, quote!(#name));
+            quote! { #value? }
+        }
+        AdditionalType::Option => quote! { this.#field.map(|x| #item).transpose()? },
+        AdditionalType::OptionVec => quote! {
+            this.#field
+                .map(|x| x.into_iter().enumerate().map(|(i, x)| #indexed).collect::<Result<_, _>>())
+                .transpose()?
+        },
+        AdditionalType::Vec => quote! {
+            this.#field.into_iter().enumerate().map(|(i, x)| #indexed).collect::<Result<_, _>>()?
+        },
+        AdditionalType::VecOption => quote! {
+            this.#field
+                .into_iter()
+                .enumerate()
+                .map(|(i, x)| x.map(|x| #indexed).transpose())
+                .collect::<Result<_, _>>()?
+        },
+    }
+}
+
+/// `if cond { then } else { else }`, where `.` is `this.field`
+fn build_map_if_expr(field: &Ident, map_if: MapIf) -> Result<TokenStream> {
+    let cond = parse_map_expr(field.clone(), map_if.cond)?;
+    let then = parse_map_expr(field.clone(), map_if.then)?;
+    let otherwise = parse_map_expr(field.clone(), map_if.otherwise)?;
+    Ok(quote! { if #cond { #then } else { #otherwise } })
+}
+
+fn build_unwrap_expr(
+    field: &Ident,
+    unwrap: Unwrap,
+    fallible: bool,
+    message: String,
+) -> Result<TokenStream> {
+    match unwrap {
+        Unwrap::Default => Ok(quote! { this.#field.unwrap_or_default() }),
+        Unwrap::Expect => Ok(quote! { this.#field.expect(#message) }),
+        Unwrap::Required if fallible => Ok(quote! {
+            this.#field.ok_or_else(|| #message.to_string())?
+        }),
+        Unwrap::Required => Err(syn::Error::new(
+            field.span(),
+            "`unwrap = required` requires a fallible conversion, use `try_from` or `try_into`",
+        )),
+        Unwrap::Or(expr) => {
+            let span = expr.span();
+            let expr = parse_map_expr(field.clone(), expr)?;
+            // the closure runs after the field is moved by `unwrap_or_else`
+            if field_uses(expr.to_token_stream())
+                .iter()
+                .any(|(f, _)| f == field)
+            {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`unwrap_or` can not use the field `{field}` itself, it is moved by the unwrap"),
+                ));
+            }
+            Ok(quote! { this.#field.unwrap_or_else(|| #expr) })
+        }
+    }
+}
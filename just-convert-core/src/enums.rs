@@ -0,0 +1,739 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{ext::IdentExt, spanned::Spanned, Path, Result};
+
+use crate::{
+    parse::edit_distance,
+    path_to_string,
+    tag::{build_from_tagged, build_into_tagged},
+    Params, PathParams, VariantParams, Variants,
+};
+
+const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Values accepted by `rename_all`
+pub(crate) const RENAME_ALL_CASES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Type on the other side of an enum conversion
+enum Target {
+    /// Primitive integer, the variants are matched by the discriminant
+    Integer,
+    /// `String`, the variants are matched by the name
+    String,
+    /// Another enum, the variants are matched by the name and their fields are converted
+    Enum,
+}
+
+impl Target {
+    fn of(path: &Path) -> Result<Self> {
+        let is_integer = path
+            .get_ident()
+            .is_some_and(|ident| INTEGER_TYPES.iter().any(|ty| ident == ty));
+        if is_integer {
+            return Ok(Self::Integer);
+        }
+        if path.is_ident("String") {
+            return Ok(Self::String);
+        }
+
+        // enums are `PascalCase`, a lowercase name is a primitive or a typo like a name close to `String`
+        if let Some(ident) = path.get_ident() {
+            let name = ident.to_string();
+            let lowercase = name.starts_with(|c: char| c.is_ascii_lowercase());
+            let string_like = name.eq_ignore_ascii_case("string")
+                || name.len() >= 3 && "String".starts_with(name.as_str());
+            if lowercase || string_like {
+                let closest = INTEGER_TYPES
+                    .iter()
+                    .chain(&["String"])
+                    .map(|ty| (edit_distance(&name, ty), ty))
+                    .filter(|(distance, _)| *distance <= 2 && *distance < name.len())
+                    .min_by_key(|(distance, _)| *distance);
+                let hint = match closest {
+                    Some((_, closest)) => format!(", did you mean `{closest}`?"),
+                    None => ",".to_string(),
+                };
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "unsupported enum conversion `{name}`{hint} \
+                         enums are converted from and into the primitive integers, `String`, \
+                         other enums, or structs with `tag`"
+                    ),
+                ));
+            }
+        }
+
+        Ok(Self::Enum)
+    }
+
+    /// `rename_all` and `case_insensitive` apply only to the names (and tags),
+    /// `exhaustive` and the variant mappings only to the enums which have finite values
+    fn check_options(&self, target: &PathParams, variants: &Variants) -> Result<()> {
+        if !matches!(self, Self::Enum) {
+            if let Some(exhaustive) = &target.exhaustive {
+                return Err(syn::Error::new(
+                    exhaustive.span(),
+                    "`exhaustive` is supported only for conversions between enums",
+                ));
+            }
+            if let Some(tag) = &target.tag {
+                return Err(syn::Error::new(
+                    tag.span(),
+                    "`tag` is supported only for conversions from and into structs",
+                ));
+            }
+            let mut mappings = variants.iter().flat_map(|(_, params)| {
+                let from = params.from.iter().map(|(path, _)| path);
+                from.chain(params.into.iter().map(|(path, _)| path))
+            });
+            if let Some(path) = mappings.find(|path| **path == target.path) {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "`variants` is supported only for conversions between enums",
+                ));
+            }
+        }
+        // the tag holds the variant name
+        if matches!(self, Self::String) || target.tag.is_some() {
+            return Ok(());
+        }
+        if let Some(rename_all) = &target.rename_all {
+            return Err(syn::Error::new_spanned(
+                rename_all,
+                "`rename_all` is supported only for conversions from and into `String`",
+            ));
+        }
+        if let Some(case_insensitive) = &target.case_insensitive {
+            return Err(syn::Error::new(
+                case_insensitive.span(),
+                "`case_insensitive` is supported only for conversions from `String`",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Params {
+    /// Conversions of the annotated enum
+    pub(crate) fn build_enum(&self, variants: &Variants) -> Result<TokenStream> {
+        let mut items = vec![];
+
+        for from in &self.from {
+            let target = Target::of(&from.path)?;
+            target.check_options(from, variants)?;
+            let item = match target {
+                Target::Integer => build_from_integer(&self.name, from, variants)?,
+                Target::String => build_from_string(&self.name, from, variants)?,
+                Target::Enum if from.tag.is_some() => {
+                    build_from_tagged(&self.name, from, variants)?
+                }
+                Target::Enum => build_from_enum(&self.name, from, variants)?,
+            };
+            items.push(item);
+        }
+
+        for into in &self.into {
+            let target = Target::of(&into.path)?;
+            target.check_options(into, variants)?;
+            let item = match target {
+                Target::Integer => build_into_integer(&self.name, into, variants)?,
+                Target::String => build_into_string(&self.name, into, variants)?,
+                Target::Enum if into.tag.is_some() => {
+                    build_into_tagged(&self.name, into, variants)?
+                }
+                Target::Enum => build_into_enum(&self.name, into, variants),
+            };
+            items.push(item);
+        }
+
+        Ok(quote! {
+            #(#items)*
+        })
+    }
+}
+
+/// `TryFrom<int>`, or `From<int>` if there is the `other` variant
+fn build_from_integer(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    check_unit_variants(variants, "an integer")?;
+    check_unique_values(variants)?;
+
+    let ty = &target.path;
+    let krate = &target.krate;
+    let fieldless = !variants.iter().any(|(_, params)| captures(params));
+    let other = other_variant(variants, target);
+    let values = variants
+        .iter()
+        .filter(|(_, params)| params.other.is_none())
+        .map(|(variant, params)| {
+            Ok((
+                variant,
+                integer_value(current, variant, params, ty, fieldless)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(other) = other {
+        let arms = values.iter().map(|(variant, value)| {
+            quote! { value if value == #value => #current::#variant, }
+        });
+        let other = other_arm(current, other);
+        return Ok(quote! {
+            impl std::convert::From<#ty> for #current {
+                fn from(value: #ty) -> Self {
+                    match value {
+                        #(#arms)*
+                        #other
+                    }
+                }
+            }
+        });
+    }
+
+    let arms = values.iter().map(|(variant, value)| {
+        quote! { value if value == #value => Ok(#current::#variant), }
+    });
+    let name = current.to_string();
+    Ok(quote! {
+        impl std::convert::TryFrom<#ty> for #current {
+            type Error = #krate::UnknownVariant;
+
+            fn try_from(value: #ty) -> Result<Self, Self::Error> {
+                match value {
+                    #(#arms)*
+                    _ => Err(#krate::UnknownVariant::new(#name, value)),
+                }
+            }
+        }
+    })
+}
+
+/// `From<Enum> for int`
+fn build_into_integer(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    check_unit_variants(variants, "an integer")?;
+
+    let ty = &target.path;
+    let fieldless = !variants.iter().any(|(_, params)| captures(params));
+    let arms = variants
+        .iter()
+        .map(|(variant, params)| {
+            if captures(params) {
+                return Ok(quote! { #current::#variant(value) => value.into(), });
+            }
+            let value = integer_value(current, variant, params, ty, fieldless)?;
+            Ok(quote! { #current::#variant => #value, })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        impl std::convert::From<#current> for #ty {
+            fn from(value: #current) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// `FromStr`, `TryFrom<&str>` and `TryFrom<String>`,
+/// or `From<&str>` and `From<String>` if there is the `other` variant
+fn build_from_string(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    check_unit_variants(variants, "a string")?;
+
+    let other = other_variant(variants, target);
+    let wrap = |value: TokenStream| match other {
+        Some(_) => value,
+        None => quote! { Ok(#value) },
+    };
+    let arms = parse_names(variants, target)?
+        .into_iter()
+        .map(|(variant, _, names)| {
+            let pattern = name_pattern(&names, target);
+            let value = wrap(quote! { #current::#variant });
+            quote! { #pattern => #value, }
+        });
+    let arms = quote! { #(#arms)* };
+
+    if let Some(other) = other {
+        let other = other_arm(current, other);
+        return Ok(quote! {
+            impl std::convert::From<&str> for #current {
+                fn from(value: &str) -> Self {
+                    match value {
+                        #arms
+                        #other
+                    }
+                }
+            }
+
+            impl std::convert::From<String> for #current {
+                fn from(value: String) -> Self {
+                    Self::from(value.as_str())
+                }
+            }
+
+            impl std::str::FromStr for #current {
+                type Err = std::convert::Infallible;
+
+                fn from_str(value: &str) -> Result<Self, Self::Err> {
+                    Ok(Self::from(value))
+                }
+            }
+        });
+    }
+
+    let name = current.to_string();
+    let krate = &target.krate;
+    Ok(quote! {
+        impl std::str::FromStr for #current {
+            type Err = #krate::UnknownVariant;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    #arms
+                    _ => Err(#krate::UnknownVariant::new(#name, value)),
+                }
+            }
+        }
+
+        impl std::convert::TryFrom<&str> for #current {
+            type Error = #krate::UnknownVariant;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+
+        impl std::convert::TryFrom<String> for #current {
+            type Error = #krate::UnknownVariant;
+
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+    })
+}
+
+/// `Display` and `From<Enum> for String`
+fn build_into_string(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    check_unit_variants(variants, "a string")?;
+    if let Some(case_insensitive) = &target.case_insensitive {
+        return Err(syn::Error::new(
+            case_insensitive.span(),
+            "`case_insensitive` is supported only for conversions from `String`",
+        ));
+    }
+
+    let arms = variants.iter().map(|(variant, params)| {
+        if captures(params) {
+            return quote! { #current::#variant(value) => std::convert::AsRef::<str>::as_ref(value), };
+        }
+        let name = variant_name(variant, params, target);
+        quote! { #current::#variant => #name, }
+    });
+
+    Ok(quote! {
+        impl std::fmt::Display for #current {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let value = match self {
+                    #(#arms)*
+                };
+                f.pad(value)
+            }
+        }
+
+        impl std::convert::From<#current> for String {
+            fn from(value: #current) -> Self {
+                value.to_string()
+            }
+        }
+    })
+}
+
+/// `From<Other> for Enum`, the variants without a match go to the `other` variant
+fn build_from_enum(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    let ty = &target.path;
+    let other = other_variant(variants, target);
+
+    let mut arms = vec![];
+    let mut mapped: Vec<(&Ident, &Ident)> = vec![];
+    for (variant, params) in variants {
+        // the `other` variant is matched by name only when it is listed explicitly,
+        // the `none` variant is converted only from `None`
+        let is_listed = params.from.iter().any(|(path, _)| path == ty);
+        if (params.other.is_some() && !is_listed) || params.none.is_some() {
+            continue;
+        }
+
+        for source in params.sources(variant, ty) {
+            if let Some((_, first)) = mapped.iter().find(|(mapped, _)| *mapped == source) {
+                let path = path_to_string(ty);
+                return Err(syn::Error::new(
+                    source.span(),
+                    format!(
+                        "`{path}::{source}` is already converted into `{first}`, \
+                         this mapping is unreachable"
+                    ),
+                ));
+            }
+            mapped.push((source, variant));
+            arms.push(variant_arm(
+                (ty, source),
+                (current, variant),
+                &params.fields,
+                |value| value,
+            ));
+        }
+    }
+    // the other enum may have no variants left for the catch-all arm
+    let other = other.map(|other| {
+        let arm = other_arm(current, other);
+        quote! { #[allow(unreachable_patterns)] #arm }
+    });
+
+    let from_option = none_variant(variants).map(|none| {
+        quote! {
+            impl std::convert::From<Option<#ty>> for #current {
+                fn from(value: Option<#ty>) -> Self {
+                    match value {
+                        Some(value) => value.into(),
+                        None => #current::#none,
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl std::convert::From<#ty> for #current {
+            fn from(value: #ty) -> Self {
+                match value {
+                    #(#arms)*
+                    #other
+                }
+            }
+        }
+
+        #from_option
+    })
+}
+
+/// `From<Enum> for Other`, the `other` variant gives back the raw value it holds.
+/// With the `none` variant it is `From<Enum> for Option<Other>`
+/// and `TryFrom<Enum> for Other`, which fails for the `none` variant
+fn build_into_enum(current: &Ident, target: &PathParams, variants: &Variants) -> TokenStream {
+    let ty = &target.path;
+    let krate = &target.krate;
+    let none = none_variant(variants);
+    let wrap = |value: TokenStream| match none {
+        Some(_) => quote! { Some(#value) },
+        None => value,
+    };
+    let arms = variants.iter().map(|(variant, params)| {
+        if params.none.is_some() {
+            return quote! { #current::#variant => None, };
+        }
+        if captures(params) {
+            let value = wrap(quote! { value.into() });
+            return quote! { #current::#variant(value) => #value, };
+        }
+        let target = params.target(variant, ty);
+        variant_arm((current, variant), (ty, target), &params.fields, wrap)
+    });
+    let result = match none {
+        Some(_) => quote! { Option<#ty> },
+        None => quote! { #ty },
+    };
+    let try_into = none.map(|none| {
+        let name = path_to_string(ty);
+        let none = format!("{current}::{none}");
+        quote! {
+            impl std::convert::TryFrom<#current> for #ty {
+                type Error = #krate::UnknownVariant;
+
+                fn try_from(value: #current) -> Result<Self, Self::Error> {
+                    Option::<#ty>::from(value)
+                        .ok_or_else(|| #krate::UnknownVariant::new(#name, #none))
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl std::convert::From<#current> for #result {
+            fn from(value: #current) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+
+        #try_into
+    }
+}
+
+/// `From::A(a, b) => To::B(a.into(), b.into())`, the value is passed through `wrap`
+fn variant_arm(
+    (from, from_variant): (&impl ToTokens, &Ident),
+    (to, to_variant): (&impl ToTokens, &Ident),
+    fields: &syn::Fields,
+    wrap: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+    let (pattern, value) = match fields {
+        syn::Fields::Unit => (quote! { #from::#from_variant }, quote! { #to::#to_variant }),
+        syn::Fields::Unnamed(fields) => {
+            let values = (0..fields.unnamed.len()).map(|i| format_ident!("v{i}"));
+            let values2 = values.clone();
+            (
+                quote! { #from::#from_variant(#(#values),*) },
+                quote! { #to::#to_variant(#(#values2.into()),*) },
+            )
+        }
+        syn::Fields::Named(fields) => {
+            let names = fields.named.iter().filter_map(|field| field.ident.as_ref());
+            let names2 = names.clone();
+            (
+                quote! { #from::#from_variant { #(#names),* } },
+                quote! { #to::#to_variant { #(#names2: #names2.into()),* } },
+            )
+        }
+    };
+    let value = wrap(value);
+    quote! { #pattern => #value, }
+}
+
+/// The variant converted from and into `None`
+fn none_variant(variants: &Variants) -> Option<&Ident> {
+    variants
+        .iter()
+        .find(|(_, params)| params.none.is_some())
+        .map(|(variant, _)| variant)
+}
+
+/// The `other` variant, unless `exhaustive` asks to map every source value
+pub(crate) fn other_variant<'a>(
+    variants: &'a Variants,
+    target: &PathParams,
+) -> Option<&'a (Ident, VariantParams)> {
+    if target.exhaustive.is_some() {
+        return None;
+    }
+    variants.iter().find(|(_, params)| params.other.is_some())
+}
+
+/// Catch-all arm, the field of the `other` variant is filled with the raw value
+fn other_arm(current: &Ident, (other, params): &(Ident, VariantParams)) -> TokenStream {
+    if captures(params) {
+        quote! { value => #current::#other(value.into()), }
+    } else {
+        quote! { _ => #current::#other, }
+    }
+}
+
+/// Whether it is the `other` variant with a field for the raw value
+pub(crate) fn captures(params: &VariantParams) -> bool {
+    params.other.is_some() && !matches!(params.fields, syn::Fields::Unit)
+}
+
+/// Value of the variant compared with the integer
+fn integer_value(
+    current: &Ident,
+    variant: &Ident,
+    params: &VariantParams,
+    ty: &Path,
+    fieldless: bool,
+) -> Result<TokenStream> {
+    if let Some(value) = &params.value {
+        return Ok(quote! { #value });
+    }
+    // `as` casts only fieldless enums
+    if !fieldless {
+        return Err(syn::Error::new(
+            variant.span(),
+            format!(
+                "variant `{variant}` requires `value = N`, because the `other` variant has a field"
+            ),
+        ));
+    }
+    Ok(quote! { #current::#variant as #ty })
+}
+
+/// Integer values known at expansion time must match a single variant:
+/// `value = N` literals and literal discriminants, the implicit ones follow the previous
+fn check_unique_values(variants: &Variants) -> Result<()> {
+    let mut used: Vec<(i128, &Ident)> = vec![];
+    let mut previous = Some(-1);
+    for (variant, params) in variants {
+        let discriminant = match &params.discriminant {
+            Some(expr) => literal_value(expr),
+            None => previous.and_then(|previous: i128| previous.checked_add(1)),
+        };
+        previous = discriminant;
+        if params.other.is_some() {
+            continue;
+        }
+
+        let (value, span) = match &params.value {
+            Some(expr) => (literal_value(expr), expr.span()),
+            None => (discriminant, variant.span()),
+        };
+        let Some(value) = value else {
+            continue;
+        };
+        if let Some((_, first)) = used.iter().find(|(used, _)| *used == value) {
+            return Err(syn::Error::new(
+                span,
+                format!("value `{value}` is already used for the variant `{first}`"),
+            ));
+        }
+        used.push((value, variant));
+    }
+    Ok(())
+}
+
+/// Value of an integer literal like `3` or `-1`
+fn literal_value(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(int),
+            ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => literal_value(expr)?.checked_neg(),
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => literal_value(expr),
+        _ => None,
+    }
+}
+
+/// Names accepted for each variant when parsing a string, except the `other` variant
+pub(crate) fn parse_names<'a>(
+    variants: &'a Variants,
+    target: &PathParams,
+) -> Result<Vec<(&'a Ident, &'a VariantParams, Vec<String>)>> {
+    let case_insensitive = target.case_insensitive.is_some();
+    let mut names = vec![];
+    let mut used: Vec<(String, &Ident)> = vec![];
+    for (variant, params) in variants.iter().filter(|(_, p)| p.other.is_none()) {
+        let name = variant_name(variant, params, target);
+        let aliases = params
+            .aliases
+            .iter()
+            .map(|alias| (alias.value(), alias.span()));
+        let mut variant_names = vec![];
+        for (name, span) in std::iter::once((name, variant.span())).chain(aliases) {
+            let key = match case_insensitive {
+                true => name.to_ascii_lowercase(),
+                false => name.clone(),
+            };
+            if let Some((_, first)) = used.iter().find(|(used, _)| *used == key) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("`{name}` is already used for the variant `{first}`"),
+                ));
+            }
+            used.push((key, variant));
+            variant_names.push(name);
+        }
+        names.push((variant, params, variant_names));
+    }
+    Ok(names)
+}
+
+/// Pattern matching `value: &str` with any of the names
+pub(crate) fn name_pattern(names: &[String], target: &PathParams) -> TokenStream {
+    if target.case_insensitive.is_some() {
+        quote! { value if #(value.eq_ignore_ascii_case(#names))||* }
+    } else {
+        quote! { #(#names)|* }
+    }
+}
+
+/// Name of the variant in string conversions: `rename`, or the `rename_all` case of the ident
+pub(crate) fn variant_name(variant: &Ident, params: &VariantParams, target: &PathParams) -> String {
+    if let Some(rename) = &params.rename {
+        return rename.value();
+    }
+    let name = variant.unraw().to_string();
+    match &target.rename_all {
+        Some(case) => rename_case(&name, &case.value()),
+        None => name,
+    }
+}
+
+/// Convert a `PascalCase` variant name to one of the `RENAME_ALL_CASES`
+fn rename_case(name: &str, case: &str) -> String {
+    let mut snake = String::new();
+    for (i, ch) in name.char_indices() {
+        if i > 0 && ch.is_uppercase() {
+            snake.push('_');
+        }
+        snake.extend(ch.to_lowercase());
+    }
+
+    match case {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "camelCase" => {
+            let mut chars = name.chars();
+            let first = chars.next().map(|ch| ch.to_lowercase().to_string());
+            first.unwrap_or_default() + chars.as_str()
+        }
+        "snake_case" => snake,
+        "SCREAMING_SNAKE_CASE" => snake.to_uppercase(),
+        "kebab-case" => snake.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Integer and string conversions have no place for the fields,
+/// so only the `other` variant can have one for the raw value
+fn check_unit_variants(variants: &Variants, target: &str) -> Result<()> {
+    for (variant, params) in variants {
+        if !matches!(params.fields, syn::Fields::Unit) && params.other.is_none() {
+            return Err(syn::Error::new(
+                variant.span(),
+                format!(
+                    "variant `{variant}` must be a unit variant to convert from or into {target}"
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
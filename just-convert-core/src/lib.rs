@@ -0,0 +1,501 @@
+//! Parsing and code generation behind the just-convert derive macro.
+//!
+//! It works on `proc_macro2` tokens, so the expansion can be tested
+//! without the compiler. Use the `just-convert` crate for the macro itself.
+
+use std::collections::HashMap;
+
+use parse::parse_params;
+use syn::{DeriveInput, Ident, Path};
+
+mod assign;
+mod build;
+mod enums;
+mod map;
+mod parse;
+mod tag;
+mod variant;
+
+/// Expand `#[derive(JustConvert)]` for already parsed input
+pub fn build_impl(input: DeriveInput) -> proc_macro2::TokenStream {
+    let params = match parse_params(&input) {
+        Ok(p) => p,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    params
+        .build()
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+/// Expand `#[derive(JustConvert)]` for the tokens of the item
+pub fn expand(input: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match syn::parse2(input) {
+        Ok(input) => build_impl(input),
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+#[derive(Debug)]
+struct Params {
+    name: Ident,
+    from: Vec<PathParams>,
+    into: Vec<PathParams>,
+    generate: Vec<Generate>,
+    /// Struct with all fields optional to update the annotated one
+    patch: Option<Generate>,
+    fields: Fields,
+    /// Variants of the annotated enum, `None` for structs
+    variants: Option<Variants>,
+}
+
+/// Struct generated from the annotated one
+#[derive(Debug)]
+struct Generate {
+    name: Ident,
+    derives: Vec<Path>,
+    vis: syn::Visibility,
+}
+
+#[derive(Debug, Clone)]
+struct PathParams {
+    path: Path,
+    default: bool,
+    /// Generate `TryFrom`/`TryInto` instead of `From`/`Into`
+    fallible: bool,
+    /// Generate `AsyncFrom` instead of `From`
+    is_async: bool,
+    /// Wrap plain values into `Some` when the target field is an `Option`
+    wrap_option: Option<Ident>,
+    /// Unwrap `Option` values when the target field is not an `Option`
+    unwrap_option: Option<Ident>,
+    /// Generate only `FromWithBase`/`IntoWithBase`,
+    /// the unmapped fields are always taken from the base value
+    base: Option<Ident>,
+    /// Generate `FromWithBase`/`IntoWithBase` in addition to `From`/`Into`
+    with_base: Option<Ident>,
+    /// Generate only `FromWithContext`/`IntoWithContext` with this context type
+    context: Option<syn::Type>,
+    /// Function called with `&mut` of the built value
+    after: Option<Path>,
+    /// Function called with `&` of the built value, makes the conversion fallible
+    validate: Option<Path>,
+    /// `rename_all = "snake_case"`, case of the variant names in enum string conversions
+    rename_all: Option<syn::LitStr>,
+    /// Match the variant names ignoring ASCII case when parsing enums from strings
+    case_insensitive: Option<Ident>,
+    /// No catch-all arm for the `other` variant, so every source variant has to be mapped
+    exhaustive: Option<Ident>,
+    /// The path is an enum variant, e.g. `Event::Created`
+    variant: Option<VariantShape>,
+    /// `tag = kind`, the enum is a flat struct with the variant name in this field
+    tag: Option<Ident>,
+    /// The path is the struct declared by `generate`, so its field types are known
+    generated: bool,
+    /// Path of the `just_convert` crate in the generated code, `crate = path` on the container
+    krate: Path,
+}
+
+impl PathParams {
+    fn new(path: Path) -> Self {
+        Self {
+            path,
+            default: false,
+            fallible: false,
+            is_async: false,
+            wrap_option: None,
+            unwrap_option: None,
+            base: None,
+            with_base: None,
+            context: None,
+            after: None,
+            validate: None,
+            rename_all: None,
+            case_insensitive: None,
+            exhaustive: None,
+            variant: None,
+            tag: None,
+            generated: false,
+            krate: syn::parse_quote!(::just_convert),
+        }
+    }
+}
+
+/// Enum variant the annotated struct is wrapped into
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VariantShape {
+    /// `Event::Created(value)`, the struct is the only field of the variant
+    Tuple,
+    /// `Event::Created { .. }` declared with `fields`, converted field by field
+    Struct,
+}
+
+#[derive(Debug, Clone)]
+struct FieldParams {
+    map: FieldValue<proc_macro2::Literal>,
+    rename: FieldValue<Ident>,
+    wrap: FieldValue<bool>,
+    unwrap: FieldValue<Unwrap>,
+    skip: FieldValue<bool>,
+    /// `skip_if = "expr"`, filled like a skipped field when the condition is true
+    skip_if: FieldValue<proc_macro2::Literal>,
+    /// `map_if(cond = "..", then = "..", else = "..")`
+    map_if: FieldValue<MapIf>,
+    /// Function called with the value (and the context, if any)
+    with: FieldValue<Path>,
+    /// Async function awaited with the value
+    await_with: FieldValue<Path>,
+    /// The `Option` is converted as a whole, `None` is a `#[convert(none)]` variant of the other side
+    none_variant: FieldValue<bool>,
+    /// `cast`, same as `as`
+    cast: FieldValue<bool>,
+    /// `saturating`, values out of range are clamped to the bounds of the target type
+    saturating: FieldValue<bool>,
+    /// `wrapping`, integers wrap around the bounds of the target type
+    wrapping: FieldValue<bool>,
+    /// `checked`, values out of range are a conversion error
+    checked: FieldValue<bool>,
+    /// `display`, the value is converted with `to_string()`
+    display: FieldValue<bool>,
+    /// `parse`, the value is converted with `str::parse`, parse errors are conversion errors
+    parse: FieldValue<bool>,
+    /// Field type in the generated struct
+    ty: FieldValue<syn::Type>,
+    /// Type of the annotated field
+    field_ty: syn::Type,
+    a_type: AdditionalType,
+}
+
+impl FieldParams {
+    fn new(field_ty: syn::Type) -> Self {
+        Self {
+            map: FieldValue::new(),
+            rename: FieldValue::new(),
+            wrap: FieldValue::new(),
+            unwrap: FieldValue::new(),
+            skip: FieldValue::new(),
+            skip_if: FieldValue::new(),
+            map_if: FieldValue::new(),
+            with: FieldValue::new(),
+            await_with: FieldValue::new(),
+            none_variant: FieldValue::new(),
+            cast: FieldValue::new(),
+            saturating: FieldValue::new(),
+            wrapping: FieldValue::new(),
+            checked: FieldValue::new(),
+            display: FieldValue::new(),
+            parse: FieldValue::new(),
+            ty: FieldValue::new(),
+            field_ty,
+            a_type: AdditionalType::None,
+        }
+    }
+
+    /// Specificity level of each option used for `from(path)`
+    fn levels_from(&self, path: &Path) -> [(&'static str, Option<u8>); 16] {
+        [
+            ("skip", self.skip.level_from(path)),
+            ("skip_if", self.skip_if.level_from(path)),
+            ("map", self.map.level_from(path)),
+            ("map_if", self.map_if.level_from(path)),
+            ("with", self.with.level_from(path)),
+            ("await_with", self.await_with.level_from(path)),
+            ("none_variant", self.none_variant.level_from(path)),
+            ("cast", self.cast.level_from(path)),
+            ("saturating", self.saturating.level_from(path)),
+            ("wrapping", self.wrapping.level_from(path)),
+            ("checked", self.checked.level_from(path)),
+            ("display", self.display.level_from(path)),
+            ("parse", self.parse.level_from(path)),
+            ("rename", self.rename.level_from(path)),
+            ("wrap", self.wrap.level_from(path)),
+            ("unwrap", self.unwrap.level_from(path)),
+        ]
+    }
+
+    /// Specificity level of each option used for `into(path)`
+    fn levels_into(&self, path: &Path) -> [(&'static str, Option<u8>); 16] {
+        [
+            ("skip", self.skip.level_into(path)),
+            ("skip_if", self.skip_if.level_into(path)),
+            ("map", self.map.level_into(path)),
+            ("map_if", self.map_if.level_into(path)),
+            ("with", self.with.level_into(path)),
+            ("await_with", self.await_with.level_into(path)),
+            ("none_variant", self.none_variant.level_into(path)),
+            ("cast", self.cast.level_into(path)),
+            ("saturating", self.saturating.level_into(path)),
+            ("wrapping", self.wrapping.level_into(path)),
+            ("checked", self.checked.level_into(path)),
+            ("display", self.display.level_into(path)),
+            ("parse", self.parse.level_into(path)),
+            ("rename", self.rename.level_into(path)),
+            ("wrap", self.wrap.level_into(path)),
+            ("unwrap", self.unwrap.level_into(path)),
+        ]
+    }
+
+    /// Paths used in `from(Path, ...)` and `into(Path, ...)` specialisations
+    fn specialised_paths(&self) -> (Vec<&Path>, Vec<&Path>) {
+        let from = (self.skip.from.keys())
+            .chain(self.skip_if.from.keys())
+            .chain(self.map.from.keys())
+            .chain(self.map_if.from.keys())
+            .chain(self.with.from.keys())
+            .chain(self.await_with.from.keys())
+            .chain(self.none_variant.from.keys())
+            .chain(self.cast.from.keys())
+            .chain(self.saturating.from.keys())
+            .chain(self.wrapping.from.keys())
+            .chain(self.checked.from.keys())
+            .chain(self.display.from.keys())
+            .chain(self.parse.from.keys())
+            .chain(self.rename.from.keys())
+            .chain(self.wrap.from.keys())
+            .chain(self.unwrap.from.keys())
+            .chain(self.ty.from.keys())
+            .collect();
+        let into = (self.skip.into.keys())
+            .chain(self.skip_if.into.keys())
+            .chain(self.map.into.keys())
+            .chain(self.map_if.into.keys())
+            .chain(self.with.into.keys())
+            .chain(self.await_with.into.keys())
+            .chain(self.none_variant.into.keys())
+            .chain(self.cast.into.keys())
+            .chain(self.saturating.into.keys())
+            .chain(self.wrapping.into.keys())
+            .chain(self.checked.into.keys())
+            .chain(self.display.into.keys())
+            .chain(self.parse.into.keys())
+            .chain(self.rename.into.keys())
+            .chain(self.wrap.into.keys())
+            .chain(self.unwrap.into.keys())
+            .chain(self.ty.into.keys())
+            .collect();
+        (from, into)
+    }
+}
+
+/// Conversion of the field value used instead of `.into()`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Conversion {
+    /// Numeric conversions of `just_convert::NumConvert` with an explicit overflow policy
+    Cast,
+    Saturating,
+    Wrapping,
+    Checked,
+    /// `to_string()`
+    Display,
+    /// `str::parse`
+    Parse,
+}
+
+impl Conversion {
+    /// Name of the field option
+    fn name(self) -> &'static str {
+        match self {
+            Conversion::Cast => "cast",
+            Conversion::Saturating => "saturating",
+            Conversion::Wrapping => "wrapping",
+            Conversion::Checked => "checked",
+            Conversion::Display => "display",
+            Conversion::Parse => "parse",
+        }
+    }
+}
+
+/// How to get a value out of the `Option` source field
+#[derive(Debug, Clone)]
+enum Unwrap {
+    /// `unwrap = default`, use `Default` value if `None`
+    Default,
+    /// `unwrap = expect`, panic with a message naming the field
+    Expect,
+    /// `unwrap = required`, return a conversion error if `None`
+    Required,
+    /// `unwrap_or = "expr"`, use the expression if `None`
+    Or(proc_macro2::Literal),
+}
+
+/// Value of `map_if(cond = "..", then = "..", else = "..")`
+#[derive(Debug, Clone)]
+struct MapIf {
+    cond: proc_macro2::Literal,
+    then: proc_macro2::Literal,
+    otherwise: proc_macro2::Literal,
+}
+
+#[derive(Debug, Default, Clone)]
+struct FieldValue<T> {
+    common: Option<T>,
+    common_from: Option<T>,
+    common_into: Option<T>,
+    from: HashMap<Path, T>,
+    into: HashMap<Path, T>,
+}
+
+impl<T> FieldValue<T> {
+    fn new() -> Self {
+        Self {
+            common: None,
+            common_from: None,
+            common_into: None,
+            from: [].into(),
+            into: [].into(),
+        }
+    }
+
+    /// Returns `false` if the value was already set
+    fn set_common(&mut self, value: T) -> bool {
+        self.common.replace(value).is_none()
+    }
+
+    /// Returns `false` if the value was already set
+    fn set_from(&mut self, path: Option<Path>, value: T) -> bool {
+        if let Some(path) = path {
+            self.from.insert(path, value).is_none()
+        } else {
+            self.common_from.replace(value).is_none()
+        }
+    }
+
+    /// Returns `false` if the value was already set
+    fn set_into(&mut self, path: Option<Path>, value: T) -> bool {
+        if let Some(path) = path {
+            self.into.insert(path, value).is_none()
+        } else {
+            self.common_into.replace(value).is_none()
+        }
+    }
+
+    /// How specific is the value used for `from(path)`:
+    /// 0 - for the path, 1 - for all `from`, 2 - common
+    fn level_from(&self, path: &Path) -> Option<u8> {
+        if self.from.contains_key(path) {
+            Some(0)
+        } else if self.common_from.is_some() {
+            Some(1)
+        } else {
+            self.common.as_ref().map(|_| 2)
+        }
+    }
+
+    /// How specific is the value used for `into(path)`:
+    /// 0 - for the path, 1 - for all `into`, 2 - common
+    fn level_into(&self, path: &Path) -> Option<u8> {
+        if self.into.contains_key(path) {
+            Some(0)
+        } else if self.common_into.is_some() {
+            Some(1)
+        } else {
+            self.common.as_ref().map(|_| 2)
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+enum AdditionalType {
+    #[default]
+    None,
+    /// Option<T>
+    Option,
+    /// Option<Vec<T>>
+    OptionVec,
+    /// Vec<T>
+    Vec,
+    /// Vec<Option<T>>
+    VecOption,
+}
+
+impl AdditionalType {
+    fn is_none(self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    fn is_option(self) -> bool {
+        matches!(self, Self::Option)
+    }
+
+    fn is_option_vec(self) -> bool {
+        matches!(self, Self::OptionVec)
+    }
+
+    fn is_vec(self) -> bool {
+        matches!(self, Self::Vec)
+    }
+
+    fn is_vec_option(self) -> bool {
+        matches!(self, Self::VecOption)
+    }
+}
+
+/// Fields in declaration order
+type Fields = Vec<(Ident, FieldParams)>;
+
+#[derive(Debug, Clone)]
+struct VariantParams {
+    fields: syn::Fields,
+    /// `value = N`, used instead of the discriminant in integer conversions
+    value: Option<syn::Expr>,
+    /// `Variant = N` of the enum declaration
+    discriminant: Option<syn::Expr>,
+    /// `none`, the variant stands for `None` of `Option<Other>` in enum conversions
+    none: Option<Ident>,
+    /// Catch-all variant for the values which match no other variant,
+    /// a single field of it receives the raw value
+    other: Option<Ident>,
+    /// `rename = "name"`, used instead of the variant name in string conversions
+    rename: Option<syn::LitStr>,
+    /// `alias("a", "b")`, additional names accepted when parsing from a string
+    aliases: Vec<syn::LitStr>,
+    /// `from(Path, variants(A, B))`, variants of the other enum converted into this one
+    from: Vec<(Path, Vec<Ident>)>,
+    /// `into(Path, variants(A))`, variant of the other enum this one is converted into
+    into: Vec<(Path, Ident)>,
+    /// Fields of the `tag` struct holding the variant fields, set with `#[convert(rename = name)]`
+    /// on a variant field, `None` for an unnamed field without it
+    tag_fields: Vec<Option<Ident>>,
+}
+
+impl VariantParams {
+    fn new(fields: syn::Fields) -> Self {
+        Self {
+            fields,
+            value: None,
+            discriminant: None,
+            none: None,
+            other: None,
+            rename: None,
+            aliases: vec![],
+            from: vec![],
+            into: vec![],
+            tag_fields: vec![],
+        }
+    }
+
+    /// Variants of `path` converted into this one, the same name by default
+    fn sources<'a>(&'a self, variant: &'a Ident, path: &Path) -> &'a [Ident] {
+        match self.from.iter().find(|(from, _)| from == path) {
+            Some((_, sources)) => sources,
+            None => std::slice::from_ref(variant),
+        }
+    }
+
+    /// Variant of `path` this one is converted into, the same name by default
+    fn target<'a>(&'a self, variant: &'a Ident, path: &Path) -> &'a Ident {
+        match self.into.iter().find(|(into, _)| into == path) {
+            Some((_, target)) => target,
+            None => variant,
+        }
+    }
+}
+
+/// Variants in declaration order
+type Variants = Vec<(Ident, VariantParams)>;
+
+/// Path as written by the user, e.g. `other::Mouse`
+fn path_to_string(path: &Path) -> String {
+    let segments: Vec<_> = path.segments.iter().map(|s| s.ident.to_string()).collect();
+    segments.join("::")
+}
@@ -0,0 +1,307 @@
+use proc_macro2::{Delimiter, Group, Literal, Punct, Spacing, TokenStream, TokenTree};
+use quote::{format_ident, quote_spanned};
+use syn::{
+    braced, bracketed,
+    ext::IdentExt,
+    parenthesized,
+    parse::{ParseStream, Parser},
+    token, Expr, Ident, LitStr, Token,
+};
+
+/// Parse the expression where `.` is the `this.field_name`
+pub(crate) fn parse_map_expr(field_name: Ident, input: Literal) -> syn::Result<Expr> {
+    parse_map_expr_with(quote_spanned!(field_name.span()=> this.#field_name), input)
+}
+
+/// Parse the expression where `.` is replaced with the `dot` tokens
+pub(crate) fn parse_map_expr_with(dot: TokenStream, input: Literal) -> syn::Result<Expr> {
+    let expr_str = syn::parse2::<LitStr>(TokenTree::Literal(input.clone()).into())?.value();
+    let parser = |input: ParseStream| {
+        let tt = parse_custom_fn_expr(&dot, input)?;
+        syn::parse2::<Expr>(tt)
+    };
+    match parser.parse_str(&expr_str) {
+        Ok(expr) => Ok(expr),
+        Err(err) => {
+            let message = format!("invalid expression `{expr_str}`: {err}");
+            Err(syn::Error::new(input.span(), message))
+        }
+    }
+}
+
+fn parse_custom_fn_expr(dot: &TokenStream, input: ParseStream) -> syn::Result<TokenStream> {
+    let mut begin_expr = true;
+    let mut tokens = Vec::new();
+    while !input.is_empty() {
+        if begin_expr {
+            if let (true, is_alone) = is_dot(&input) {
+                input.parse::<Token![.]>()?;
+                tokens.extend(dot.clone());
+                if !is_alone {
+                    tokens.push(TokenTree::Punct(Punct::new('.', Spacing::Alone)));
+                }
+                begin_expr = false;
+                continue;
+            }
+        }
+
+        begin_expr = input.peek(Token![break])
+            || input.peek(Token![continue])
+            || input.peek(Token![if])
+            || input.peek(Token![in])
+            || input.peek(Token![match])
+            || input.peek(Token![mut])
+            || input.peek(Token![return])
+            || input.peek(Token![while])
+            || input.peek(Token![+])
+            || input.peek(Token![&])
+            || input.peek(Token![!])
+            || input.peek(Token![^])
+            || input.peek(Token![,])
+            || input.peek(Token![/])
+            || input.peek(Token![=])
+            || input.peek(Token![>])
+            || input.peek(Token![<])
+            || input.peek(Token![|])
+            || input.peek(Token![%])
+            || input.peek(Token![;])
+            || input.peek(Token![*])
+            || input.peek(Token![-]);
+
+        let token: TokenTree = if input.peek(token::Paren) {
+            let content;
+            let delimiter = parenthesized!(content in input);
+            let nested = parse_custom_fn_expr(dot, &content)?;
+            let mut group = Group::new(Delimiter::Parenthesis, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else if input.peek(token::Brace) {
+            let content;
+            let delimiter = braced!(content in input);
+            let nested = parse_custom_fn_expr(dot, &content)?;
+            let mut group = Group::new(Delimiter::Brace, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else if input.peek(token::Bracket) {
+            let content;
+            let delimiter = bracketed!(content in input);
+            let nested = parse_custom_fn_expr(dot, &content)?;
+            let mut group = Group::new(Delimiter::Bracket, nested);
+            group.set_span(delimiter.span.join());
+            TokenTree::Group(group)
+        } else {
+            input.parse()?
+        };
+        tokens.push(token);
+    }
+    Ok(TokenStream::from_iter(tokens))
+}
+
+/// Return (is_dot, is_alone)
+fn is_dot(input: &ParseStream) -> (bool, bool) {
+    if !input.peek(Token![.]) {
+        return (false, false);
+    }
+
+    // e.g. ". as i32"
+    if input.peek2(Token![as]) {
+        return (true, true);
+    }
+
+    // e.g. ".clone()"
+    if input.peek2(syn::Ident) {
+        return (true, false);
+    }
+
+    // e.g. ". < 0" or ". + 1"
+    if input.peek2(Token![<])
+        || input.peek2(Token![>])
+        || input.peek2(Token![==])
+        || input.peek2(Token![!=])
+        || input.peek2(Token![+])
+        || input.peek2(Token![-])
+        || input.peek2(Token![*])
+        || input.peek2(Token![/])
+        || input.peek2(Token![%])
+        || input.peek2(Token![&&])
+        || input.peek2(Token![||])
+    {
+        return (true, true);
+    }
+
+    // e.g. ". {" or ". ("
+    if input.peek2(token::Brace) || input.peek2(token::Paren) {
+        return (true, true);
+    }
+
+    // e.g. single "."
+    // check is the next token empty
+    let input = input.fork();
+    let _ = input.parse::<Token![.]>();
+    if input.is_empty() {
+        (true, true)
+    } else {
+        (false, false)
+    }
+}
+
+/// Methods which only borrow the value, other method calls are taken as moves
+const BORROWING_METHODS: &[&str] = &[
+    "clone",
+    "to_string",
+    "to_owned",
+    "to_vec",
+    "to_lowercase",
+    "to_uppercase",
+    "trim",
+    "len",
+    "is_empty",
+    "is_some",
+    "is_none",
+    "is_ok",
+    "is_err",
+    "as_str",
+    "as_ref",
+    "as_deref",
+    "as_slice",
+    "as_bytes",
+    "iter",
+    "contains",
+    "contains_key",
+    "starts_with",
+    "ends_with",
+    "get",
+    "first",
+    "last",
+    "chars",
+    "bytes",
+    "split",
+    "lines",
+    "parse",
+    "eq",
+    "ne",
+    "cmp",
+];
+
+/// Fields referenced as `this.field` in the expression.
+/// The flag is `true` when the value looks moved: not borrowed (`&this.field`),
+/// not inside a macro and not only read by what follows, see [`moves_value`]
+pub(crate) fn field_uses(tokens: TokenStream) -> Vec<(Ident, bool)> {
+    let mut uses = vec![];
+    collect_field_uses(tokens, "this", false, &mut uses);
+    uses
+}
+
+/// Converted fields referenced as `out.field`, flagged like in [`field_uses`]
+pub(crate) fn out_field_uses(tokens: TokenStream) -> Vec<(Ident, bool)> {
+    let mut uses = vec![];
+    collect_field_uses(tokens, "out", false, &mut uses);
+    uses
+}
+
+fn collect_field_uses(
+    tokens: TokenStream,
+    receiver: &str,
+    in_macro: bool,
+    uses: &mut Vec<(Ident, bool)>,
+) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let is_punct = |i: usize, ch: char| matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == ch);
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Group(group) => {
+                let is_macro = i > 0 && is_punct(i - 1, '!');
+                collect_field_uses(group.stream(), receiver, in_macro || is_macro, uses);
+            }
+            // `this.field`, but not `other.this.field`
+            TokenTree::Ident(this)
+                if this == receiver
+                    && is_punct(i + 1, '.')
+                    && (i == 0 || !is_punct(i - 1, '.')) =>
+            {
+                let Some(TokenTree::Ident(field)) = tokens.get(i + 2) else {
+                    continue;
+                };
+                let borrowed = i > 0 && is_punct(i - 1, '&')
+                    || i > 1
+                        && is_punct(i - 2, '&')
+                        && matches!(&tokens[i - 1], TokenTree::Ident(m) if m == "mut");
+                let moved = moves_value(&tokens[i + 3..]);
+                uses.push((field.clone(), !in_macro && !borrowed && moved));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The tokens after `this.field` move its value: it is passed as a whole (`Some(this.field)`),
+/// consumed by a method call (`this.field.into_bytes()`) or an operator (`this.field + "!"`).
+/// Comparisons, casts, indexing, access of a nested field
+/// and the `BORROWING_METHODS` only read it
+fn moves_value(rest: &[TokenTree]) -> bool {
+    match rest.first() {
+        None => true,
+        Some(TokenTree::Punct(punct)) => match punct.as_char() {
+            // a range
+            '.' if matches!(rest.get(1), Some(TokenTree::Punct(p)) if p.as_char() == '.') => true,
+            '.' => match (rest.get(1), rest.get(2)) {
+                (Some(TokenTree::Ident(method)), Some(TokenTree::Group(args)))
+                    if args.delimiter() == Delimiter::Parenthesis =>
+                {
+                    !BORROWING_METHODS
+                        .iter()
+                        .any(|borrowing| method == borrowing)
+                }
+                _ => false,
+            },
+            '=' | '!' | '<' | '>' => false,
+            _ => true,
+        },
+        Some(TokenTree::Ident(ident)) => ident != "as",
+        Some(TokenTree::Group(group)) => group.delimiter() != Delimiter::Bracket,
+        Some(TokenTree::Literal(_)) => true,
+    }
+}
+
+/// Replace `out.field` with the `out_field` local holding the converted value,
+/// `out` followed by other names is left alone, e.g. a closure parameter
+pub(crate) fn bind_out_refs(tokens: TokenStream, fields: &[Ident]) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let is_punct = |i: usize, ch: char| matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == ch);
+
+    let mut result = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Group(group) => {
+                let stream = bind_out_refs(group.stream(), fields);
+                let mut replaced = Group::new(group.delimiter(), stream);
+                replaced.set_span(group.span());
+                result.push(TokenTree::Group(replaced));
+            }
+            // `out.field`, but not `this.out.field`
+            TokenTree::Ident(out)
+                if out == "out" && is_punct(i + 1, '.') && (i == 0 || !is_punct(i - 1, '.')) =>
+            {
+                match tokens.get(i + 2) {
+                    Some(TokenTree::Ident(field)) if fields.contains(field) => {
+                        result.push(TokenTree::Ident(out_ident(field)));
+                        i += 3;
+                        continue;
+                    }
+                    _ => {}
+                }
+                result.push(tokens[i].clone());
+            }
+            token => result.push(token.clone()),
+        }
+        i += 1;
+    }
+    TokenStream::from_iter(result)
+}
+
+/// Local holding the converted value of the field
+pub(crate) fn out_ident(field: &Ident) -> Ident {
+    format_ident!("out_{}", field.unraw(), span = field.span())
+}
@@ -0,0 +1,1333 @@
+use syn::{
+    meta::ParseNestedMeta, parenthesized, parse::ParseStream, token, Data, DataEnum, DeriveInput,
+    FieldsNamed, Ident, LitBool, LitStr, Path, PathSegment, Result, Token, Visibility,
+};
+
+use crate::{
+    enums::RENAME_ALL_CASES, path_to_string, AdditionalType, FieldParams, FieldValue, Fields,
+    Generate, MapIf, Params, PathParams, Unwrap, VariantParams, VariantShape, Variants,
+};
+
+pub(crate) fn parse_params(input: &DeriveInput) -> Result<Params> {
+    let mut params = Params {
+        name: input.ident.clone(),
+        from: vec![],
+        into: vec![],
+        generate: vec![],
+        patch: None,
+        fields: vec![],
+        variants: None,
+    };
+
+    parse_attributes(input, &mut params)?;
+
+    if let Data::Enum(data) = &input.data {
+        if !params.generate.is_empty() || params.patch.is_some() {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`generate` and `patch` are not supported for enums",
+            ));
+        }
+        if let Some(target) = params.from.iter().find(|target| target.variant.is_some()) {
+            return Err(syn::Error::new_spanned(
+                &target.path,
+                "only structs can be converted into enum variants",
+            ));
+        }
+        let variants = parse_variants(data)?;
+        check_variant_targets(&variants, &params)?;
+        params.variants = Some(variants);
+        return Ok(params);
+    }
+
+    check_enum_options(&params)?;
+    params.fields = parse_fields(&input.data)?;
+
+    // patch is applied like a conversion from it
+    let from: Vec<Path> = (params.from.iter().map(|t| t.path.clone()))
+        .chain(params.patch.iter().map(|p| Path::from(p.name.clone())))
+        .collect();
+    let into: Vec<Path> = params.into.iter().map(|t| t.path.clone()).collect();
+
+    check_conflicts(&params.fields, &from, &into)?;
+    check_undeclared_targets(&params.fields, &from, &into)?;
+
+    Ok(params)
+}
+
+const CONVERT_KEYS: &[&str] = &[
+    "from",
+    "into",
+    "from_into",
+    "into_from",
+    "try_from",
+    "try_into",
+    "try_from_into",
+    "try_into_from",
+    "async_from",
+    "generate",
+    "patch",
+    "crate",
+];
+
+const CONVERT_OPTIONS: &[&str] = &[
+    "default",
+    "wrap_option",
+    "unwrap_option",
+    "base",
+    "with_base",
+    "context",
+    "after",
+    "validate",
+    "rename_all",
+    "case_insensitive",
+    "exhaustive",
+    "fields",
+    "tag",
+];
+
+const FIELD_KEYS: &[&str] = &[
+    "rename",
+    "wrap",
+    "unwrap",
+    "unwrap_or",
+    "skip",
+    "skip_if",
+    "map",
+    "map_if",
+    "with",
+    "await_with",
+    "none_variant",
+    "cast",
+    "saturating",
+    "wrapping",
+    "checked",
+    "display",
+    "parse",
+    "ty",
+];
+
+const VARIANT_KEYS: &[&str] = &[
+    "value",
+    "none",
+    "other",
+    "rename",
+    "alias",
+    "from",
+    "into",
+    "from_into",
+    "into_from",
+];
+
+const VARIANT_MAPPING_KEYS: &[&str] = &["variants", "canonical"];
+
+const MAP_IF_KEYS: &[&str] = &[
+    "cond",
+    "then",
+    "else",
+    "from",
+    "into",
+    "from_into",
+    "into_from",
+];
+
+const FIELD_TARGET_KEYS: &[&str] = &["from", "into", "from_into", "into_from"];
+
+fn parse_attributes(input: &DeriveInput, params: &mut Params) -> Result<()> {
+    let mut krate: Option<Path> = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("convert") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("generate") {
+                let generate = parse_generate(&meta, &input.vis)?;
+
+                // the generated struct is converted both ways
+                let target = PathParams {
+                    generated: true,
+                    ..PathParams::new(Path::from(generate.name.clone()))
+                };
+                params.from.push(target.clone());
+                params.into.push(target);
+
+                params.generate.push(generate);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("crate") {
+                if krate.is_some() {
+                    return Err(meta.error("duplicate `crate`"));
+                }
+                krate = Some(meta.value()?.parse()?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("patch") {
+                if params.patch.is_some() {
+                    return Err(meta.error("only one `patch` can be declared"));
+                }
+                params.patch = Some(parse_generate(&meta, &input.vis)?);
+                return Ok(());
+            }
+
+            // fallible conversions are declared as `try_from(...)` etc.
+            let fallible = meta
+                .path
+                .get_ident()
+                .is_some_and(|ident| ident.to_string().starts_with("try_"));
+            // async conversions are declared as `async_from(...)`, there is no async `into`
+            let is_async = meta.path.is_ident("async_from");
+            if let Some(ident) = meta.path.get_ident() {
+                if !is_async && ident.to_string().starts_with("async_") {
+                    return Err(meta.error(format!(
+                        "unrecognized convert `{ident}`, did you mean `async_from`? \
+                         only infallible `from` conversions can be async"
+                    )));
+                }
+            }
+            let kind = meta.path.get_ident().map(|ident| {
+                let ident = ident.to_string();
+                let ident = ident.strip_prefix("async_").unwrap_or(&ident);
+                ident.strip_prefix("try_").unwrap_or(ident).to_string()
+            });
+            let kind = kind.as_deref().unwrap_or_default();
+
+            // if need to convert both for `from` and `into`
+            let is_both = kind == "from_into" || kind == "into_from";
+
+            let is_from = is_both || kind == "from";
+            let is_into = is_both || kind == "into";
+
+            if !is_from && !is_into {
+                return Err(unknown_error(
+                    &meta.path,
+                    "unrecognized convert",
+                    CONVERT_KEYS,
+                ));
+            }
+
+            // parse for path
+            let content;
+            parenthesized!(content in meta.input);
+            let mut target = PathParams::new(normalize_path(content.parse()?));
+            let mut context = None;
+            let mut fields = None;
+            target.fallible = fallible;
+            target.is_async = is_async;
+
+            while content.peek(Token![,]) {
+                content.parse::<Token![,]>()?;
+
+                if content.peek(Token![default]) {
+                    content.parse::<Token![default]>()?;
+                    target.default = true;
+                    continue;
+                }
+
+                let attr = content.parse::<Ident>()?;
+                if attr == "wrap_option" {
+                    target.wrap_option = Some(attr);
+                } else if attr == "unwrap_option" {
+                    target.unwrap_option = Some(attr);
+                } else if attr == "base" {
+                    target.base = Some(attr);
+                } else if attr == "with_base" {
+                    target.with_base = Some(attr);
+                } else if attr == "context" {
+                    content.parse::<Token![=]>()?;
+                    target.context = Some(content.parse()?);
+                    context = Some(attr);
+                } else if attr == "after" {
+                    content.parse::<Token![=]>()?;
+                    target.after = Some(content.parse()?);
+                } else if attr == "validate" {
+                    content.parse::<Token![=]>()?;
+                    target.validate = Some(content.parse()?);
+                } else if attr == "rename_all" {
+                    content.parse::<Token![=]>()?;
+                    let case: LitStr = content.parse()?;
+                    if !RENAME_ALL_CASES.contains(&case.value().as_str()) {
+                        let expected = RENAME_ALL_CASES.join("`, `");
+                        return Err(syn::Error::new_spanned(
+                            &case,
+                            format!("unknown case, expected one of `{expected}`"),
+                        ));
+                    }
+                    target.rename_all = Some(case);
+                } else if attr == "case_insensitive" {
+                    target.case_insensitive = Some(attr);
+                } else if attr == "exhaustive" {
+                    target.exhaustive = Some(attr);
+                } else if attr == "tag" {
+                    content.parse::<Token![=]>()?;
+                    target.tag = Some(content.parse()?);
+                } else if attr == "fields" {
+                    fields = Some(attr);
+                } else {
+                    return Err(unknown_error(&attr, "unknown value", CONVERT_OPTIONS));
+                }
+            }
+
+            // the validation error is returned from `TryFrom`/`TryInto`
+            if let Some(validate) = &target.validate {
+                if target.is_async {
+                    return Err(syn::Error::new_spanned(
+                        validate,
+                        "`validate` is not supported for async conversions",
+                    ));
+                }
+                target.fallible = true;
+            }
+
+            if let Some(context) = &context {
+                if target.fallible || target.is_async {
+                    return Err(syn::Error::new(
+                        context.span(),
+                        "`context` is not supported for fallible or async conversions",
+                    ));
+                }
+            }
+
+            if let (Some(_), Some(unwrap_option)) = (&target.wrap_option, &target.unwrap_option) {
+                return Err(syn::Error::new(
+                    unwrap_option.span(),
+                    "`wrap_option` and `unwrap_option` cannot be used together",
+                ));
+            }
+
+            if let (Some(_), Some(with_base)) = (&target.base, &target.with_base) {
+                return Err(syn::Error::new(
+                    with_base.span(),
+                    "`base` already generates only the base conversion, remove `with_base`",
+                ));
+            }
+
+            if let Some(base) = &target.base {
+                if target.default {
+                    return Err(syn::Error::new(
+                        base.span(),
+                        "`base` and `default` cannot be used together",
+                    ));
+                }
+            }
+
+            if let Some(base) = target.base.as_ref().or(target.with_base.as_ref()) {
+                if target.context.is_some() {
+                    return Err(syn::Error::new(
+                        base.span(),
+                        format!("`{base}` and `context` cannot be used together"),
+                    ));
+                }
+                if target.fallible || target.is_async {
+                    return Err(syn::Error::new(
+                        base.span(),
+                        format!("`{base}` is not supported for fallible or async conversions"),
+                    ));
+                }
+            }
+
+            if is_variant_path(&target.path) {
+                check_variant_target(&target, fields.is_some())?;
+                target.variant = Some(match fields {
+                    Some(_) => VariantShape::Struct,
+                    None => VariantShape::Tuple,
+                });
+            } else if let Some(fields) = fields {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "`fields` is supported only for enum variants, e.g. `Event::Created`",
+                ));
+            }
+
+            // the struct is extracted back from the variant with `TryFrom`
+            if is_from || target.variant.is_some() {
+                let mut from = target.clone();
+                from.fallible |= from.variant.is_some();
+                params.from.push(from);
+            }
+
+            if is_into {
+                params.into.push(target);
+            }
+
+            Ok(())
+        })?;
+    }
+
+    // `crate` applies to every conversion, wherever it is declared
+    if let Some(krate) = krate {
+        for target in params.from.iter_mut().chain(&mut params.into) {
+            target.krate = krate.clone();
+        }
+    }
+
+    Ok(())
+}
+
+/// Variant mappings must refer to the conversions of the enum,
+/// the `other` variant with the raw value cannot have the mappings
+fn check_variant_targets(variants: &Variants, params: &Params) -> Result<()> {
+    for (variant, variant_params) in variants {
+        let from = variant_params
+            .from
+            .iter()
+            .map(|(path, _)| ("from", path, &params.from));
+        let into = variant_params
+            .into
+            .iter()
+            .map(|(path, _)| ("into", path, &params.into));
+
+        for (direction, path, declared) in from.chain(into) {
+            if !declared.iter().any(|target| target.path == *path) {
+                let path_str = path_to_string(path);
+                return Err(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "variant `{variant}` refers to `{direction}({path_str})`, \
+                         which is not declared on the container"
+                    ),
+                ));
+            }
+            if variant_params.other.is_some() && !matches!(variant_params.fields, syn::Fields::Unit)
+            {
+                return Err(syn::Error::new_spanned(
+                    path,
+                    "the `other` variant with the raw value cannot be mapped to other variants",
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Options of the string conversions make no sense for structs
+fn check_enum_options(params: &Params) -> Result<()> {
+    for target in params.from.iter().chain(&params.into) {
+        if let Some(rename_all) = &target.rename_all {
+            return Err(syn::Error::new_spanned(
+                rename_all,
+                "`rename_all` is supported only for enums",
+            ));
+        }
+        if let Some(case_insensitive) = &target.case_insensitive {
+            return Err(syn::Error::new(
+                case_insensitive.span(),
+                "`case_insensitive` is supported only for enums",
+            ));
+        }
+        if let Some(exhaustive) = &target.exhaustive {
+            return Err(syn::Error::new(
+                exhaustive.span(),
+                "`exhaustive` is supported only for enums",
+            ));
+        }
+        if let Some(tag) = &target.tag {
+            return Err(syn::Error::new(
+                tag.span(),
+                "`tag` is supported only for enums",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `Event::Created` is a variant, while `other::Event` is a type in a module
+fn is_variant_path(path: &Path) -> bool {
+    let mut segments = path.segments.iter().rev();
+    segments.next();
+    segments.next().is_some_and(|segment| {
+        let name = segment.ident.to_string();
+        name.starts_with(|ch: char| ch.is_ascii_uppercase())
+    })
+}
+
+/// The conversion options which cannot be applied to an enum variant
+fn check_variant_target(target: &PathParams, fields: bool) -> Result<()> {
+    let unsupported = [
+        ("async_from", target.is_async),
+        ("base", target.base.is_some()),
+        ("with_base", target.with_base.is_some()),
+        ("context", target.context.is_some()),
+        ("default", target.default && fields),
+    ];
+    match unsupported.iter().find(|(_, used)| *used) {
+        Some((option, _)) => Err(syn::Error::new_spanned(
+            &target.path,
+            format!("`{option}` is not supported for enum variants"),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Parse `generate(Name, derive(Debug, Clone), vis = pub)`, same for `patch`
+fn parse_generate(meta: &ParseNestedMeta<'_>, vis: &Visibility) -> Result<Generate> {
+    let content;
+    parenthesized!(content in meta.input);
+
+    let mut generate = Generate {
+        name: content.parse()?,
+        derives: vec![],
+        vis: vis.clone(),
+    };
+
+    while content.peek(Token![,]) {
+        content.parse::<Token![,]>()?;
+
+        let key: Ident = content.parse()?;
+        if key == "derive" {
+            let derives;
+            parenthesized!(derives in content);
+            let derives = derives.parse_terminated(Path::parse_mod_style, Token![,])?;
+            generate.derives.extend(derives);
+        } else if key == "vis" {
+            content.parse::<Token![=]>()?;
+            generate.vis = content.parse()?;
+        } else {
+            return Err(unknown_error(
+                &key,
+                "unknown generate option",
+                &["derive", "vis"],
+            ));
+        }
+    }
+
+    Ok(generate)
+}
+
+fn parse_fields(data: &Data) -> Result<Fields> {
+    match data {
+        Data::Struct(s) => match &s.fields {
+            syn::Fields::Named(d) => parse_named_struct_fields(d),
+            syn::Fields::Unnamed(_) => Err(syn::Error::new(
+                s.struct_token.span,
+                "unnamed struct is not currently supported",
+            )),
+            syn::Fields::Unit => Err(syn::Error::new(
+                s.struct_token.span,
+                "unit is not currently supported",
+            )),
+        },
+        Data::Enum(_) => unreachable!("enums are parsed by `parse_variants`"),
+        Data::Union(d) => Err(syn::Error::new(
+            d.union_token.span,
+            "union is not currently supported",
+        )),
+    }
+}
+
+fn parse_variants(data: &DataEnum) -> Result<Variants> {
+    let mut variants: Variants = vec![];
+    let mut other: Option<&Ident> = None;
+    let mut none: Option<&Ident> = None;
+
+    for variant in &data.variants {
+        let mut params = VariantParams::new(variant.fields.clone());
+        params.tag_fields = parse_variant_fields(&variant.fields)?;
+        params.discriminant = variant.discriminant.as_ref().map(|(_, expr)| expr.clone());
+
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("convert") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("value") {
+                    if params.value.is_some() {
+                        return Err(meta.error("duplicate `value`"));
+                    }
+                    params.value = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("none") {
+                    if let Some(none) = none {
+                        return Err(meta.error(format!(
+                            "only one `none` variant can be declared, `{none}` is already one"
+                        )));
+                    }
+                    if !matches!(variant.fields, syn::Fields::Unit) {
+                        return Err(meta.error("the `none` variant must be a unit variant"));
+                    }
+                    none = Some(&variant.ident);
+                    params.none = meta.path.get_ident().cloned();
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("other") {
+                    if let Some(other) = other {
+                        return Err(meta.error(format!(
+                            "only one `other` variant can be declared, `{other}` is already one"
+                        )));
+                    }
+                    let supported = match &variant.fields {
+                        syn::Fields::Unit => true,
+                        syn::Fields::Unnamed(fields) => fields.unnamed.len() == 1,
+                        syn::Fields::Named(_) => false,
+                    };
+                    if !supported {
+                        return Err(meta.error(
+                            "the `other` variant must be a unit variant or a tuple variant with one field for the raw value",
+                        ));
+                    }
+                    other = Some(&variant.ident);
+                    params.other = meta.path.get_ident().cloned();
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("rename") {
+                    if params.rename.is_some() {
+                        return Err(meta.error("duplicate `rename`"));
+                    }
+                    params.rename = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                let is_both = meta.path.is_ident("from_into") || meta.path.is_ident("into_from");
+                let is_from = is_both || meta.path.is_ident("from");
+                let is_into = is_both || meta.path.is_ident("into");
+                if is_from || is_into {
+                    return parse_variant_mapping(&meta, &mut params, is_from, is_into);
+                }
+
+                if meta.path.is_ident("alias") {
+                    if meta.input.peek(token::Paren) {
+                        let content;
+                        parenthesized!(content in meta.input);
+                        let aliases = content
+                            .parse_terminated(<LitStr as syn::parse::Parse>::parse, Token![,])?;
+                        params.aliases.extend(aliases);
+                    } else {
+                        params.aliases.push(meta.value()?.parse()?);
+                    }
+                    return Ok(());
+                }
+
+                Err(unknown_error(
+                    &meta.path,
+                    "unknown variant option",
+                    VARIANT_KEYS,
+                ))
+            })?;
+        }
+
+        if let (Some(none), Some(_)) = (&params.none, &params.other) {
+            return Err(syn::Error::new(
+                none.span(),
+                "`none` and `other` cannot be used together",
+            ));
+        }
+
+        variants.push((variant.ident.clone(), params));
+    }
+
+    Ok(variants)
+}
+
+/// Parse `#[convert(rename = name)]` of the variant fields
+fn parse_variant_fields(fields: &syn::Fields) -> Result<Vec<Option<Ident>>> {
+    let mut names = vec![];
+    for field in fields {
+        let mut name = field.ident.clone();
+        for attr in &field.attrs {
+            if !attr.path().is_ident("convert") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    name = Some(meta.value()?.parse()?);
+                    return Ok(());
+                }
+
+                Err(unknown_error(
+                    &meta.path,
+                    "unknown variant field option",
+                    &["rename"],
+                ))
+            })?;
+        }
+        names.push(name);
+    }
+    Ok(names)
+}
+
+/// Parse `from(Path, variants(A, B))`, `into(Path, variants(A))`
+/// and `from_into(Path, variants(A, B), canonical = A)` of a variant
+fn parse_variant_mapping(
+    meta: &ParseNestedMeta<'_>,
+    params: &mut VariantParams,
+    is_from: bool,
+    is_into: bool,
+) -> Result<()> {
+    let content;
+    parenthesized!(content in meta.input);
+    let path = normalize_path(content.parse()?);
+    let mut sources = vec![];
+    let mut canonical: Option<Ident> = None;
+
+    while content.peek(Token![,]) {
+        content.parse::<Token![,]>()?;
+
+        let key: Ident = content.parse()?;
+        if key == "variants" {
+            let list;
+            parenthesized!(list in content);
+            sources.extend(list.parse_terminated(<Ident as syn::parse::Parse>::parse, Token![,])?);
+        } else if key == "canonical" {
+            content.parse::<Token![=]>()?;
+            canonical = Some(content.parse()?);
+        } else {
+            return Err(unknown_error(
+                &key,
+                "unknown variant mapping option",
+                VARIANT_MAPPING_KEYS,
+            ));
+        }
+    }
+
+    let path_str = path_to_string(&path);
+    if sources.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &path,
+            format!("`variants(...)` is required for the mapping of `{path_str}`"),
+        ));
+    }
+
+    if is_from {
+        if params.from.iter().any(|(from, _)| *from == path) {
+            return Err(meta.error(format!("duplicate `from({path_str})`")));
+        }
+        params.from.push((path.clone(), sources.clone()));
+    }
+
+    if is_into {
+        if params.into.iter().any(|(into, _)| *into == path) {
+            return Err(meta.error(format!("duplicate `into({path_str})`")));
+        }
+        let target = match canonical {
+            Some(canonical) if !sources.contains(&canonical) => {
+                return Err(syn::Error::new(
+                    canonical.span(),
+                    format!("`canonical` must be one of the listed variants of `{path_str}`"),
+                ));
+            }
+            Some(canonical) => canonical,
+            None if sources.len() == 1 => sources[0].clone(),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    &path,
+                    format!(
+                        "ambiguous conversion into `{path_str}`, \
+                         choose one of the variants with `canonical = ...`"
+                    ),
+                ));
+            }
+        };
+        params.into.push((path, target));
+    } else if let Some(canonical) = canonical {
+        return Err(syn::Error::new(
+            canonical.span(),
+            "`canonical` is used only for `into` conversions",
+        ));
+    }
+
+    Ok(())
+}
+
+fn parse_named_struct_fields(d: &FieldsNamed) -> Result<Fields> {
+    let mut fields: Fields = vec![];
+
+    for field in &d.named {
+        let Some(name) = field.ident.clone() else {
+            continue;
+        };
+
+        let mut field_params = FieldParams::new(field.ty.clone());
+        field_params.a_type = detect_type_for_serviced(&field.ty, 0);
+
+        for attr in &field.attrs {
+            if !attr.path().is_ident("convert") {
+                continue;
+            }
+
+            attr.parse_nested_meta(|meta| {
+                if parse_field_value("rename", &meta, &mut field_params.rename)? {
+                    return Ok(());
+                }
+
+                if parse_field_value_bool("wrap", &meta, &mut field_params.wrap)? {
+                    return Ok(());
+                }
+
+                if parse_field_value_bool("none_variant", &meta, &mut field_params.none_variant)? {
+                    return Ok(());
+                }
+
+                for (name, value) in [
+                    ("cast", &mut field_params.cast),
+                    ("saturating", &mut field_params.saturating),
+                    ("wrapping", &mut field_params.wrapping),
+                    ("checked", &mut field_params.checked),
+                    ("display", &mut field_params.display),
+                    ("parse", &mut field_params.parse),
+                ] {
+                    if parse_field_value_bool(name, &meta, value)? {
+                        return Ok(());
+                    }
+                }
+
+                if parse_field_flag(
+                    "unwrap",
+                    &meta,
+                    &mut field_params.unwrap,
+                    Unwrap::Default,
+                    parse_unwrap,
+                )? {
+                    return Ok(());
+                }
+
+                if parse_field_value_with("unwrap_or", &meta, &mut field_params.unwrap, |input| {
+                    Ok(Unwrap::Or(input.parse()?))
+                })? {
+                    return Ok(());
+                }
+
+                if parse_field_value_bool("skip", &meta, &mut field_params.skip)? {
+                    return Ok(());
+                }
+
+                if parse_field_value("skip_if", &meta, &mut field_params.skip_if)? {
+                    return Ok(());
+                }
+
+                if parse_field_value("map", &meta, &mut field_params.map)? {
+                    return Ok(());
+                }
+
+                if parse_field_map_if(&meta, &mut field_params.map_if)? {
+                    return Ok(());
+                }
+
+                if parse_field_value("with", &meta, &mut field_params.with)? {
+                    return Ok(());
+                }
+
+                if parse_field_value("await_with", &meta, &mut field_params.await_with)? {
+                    return Ok(());
+                }
+
+                if parse_field_value("ty", &meta, &mut field_params.ty)? {
+                    return Ok(());
+                }
+
+                Err(unknown_error(&meta.path, "unknown field", FIELD_KEYS))
+            })?;
+        }
+
+        fields.push((name, field_params));
+    }
+
+    Ok(fields)
+}
+
+/// Try parse value as `rename = "value"`,
+/// `rename(into = "value")` or `rename(into(Path, "value"))`
+fn parse_field_value<T>(
+    name: &'static str,
+    meta: &ParseNestedMeta<'_>,
+    field_value: &mut FieldValue<T>,
+) -> Result<bool>
+where
+    T: syn::parse::Parse + Clone,
+{
+    parse_field_value_with(name, meta, field_value, T::parse)
+}
+
+/// Same as [`parse_field_value`], but the value is parsed with `parser`
+fn parse_field_value_with<T: Clone>(
+    name: &'static str,
+    meta: &ParseNestedMeta<'_>,
+    field_value: &mut FieldValue<T>,
+    parser: fn(ParseStream) -> Result<T>,
+) -> Result<bool> {
+    if !meta.path.is_ident(name) {
+        return Ok(false);
+    }
+
+    if meta.input.peek(token::Paren) {
+        meta.parse_nested_meta(|meta| {
+            let is_both = meta.path.is_ident("from_into") || meta.path.is_ident("into_from");
+            let is_from = is_both || meta.path.is_ident("from");
+            let is_into = is_both || meta.path.is_ident("into");
+
+            if !is_from && !is_into {
+                return Err(unknown_error(
+                    &meta.path,
+                    "unknown field",
+                    FIELD_TARGET_KEYS,
+                ));
+            }
+
+            let (path, v) = parse_field_value_for(&meta, parser)?;
+            if is_from && !field_value.set_from(path.clone(), v.clone()) {
+                return Err(meta.error(format!("duplicate `{name}` for `from`")));
+            }
+            if is_into && !field_value.set_into(path, v) {
+                return Err(meta.error(format!("duplicate `{name}` for `into`")));
+            }
+            Ok(())
+        })?;
+    } else {
+        let value = parser(meta.value()?)?;
+        if !field_value.set_common(value) {
+            return Err(meta.error(format!("duplicate `{name}`")));
+        }
+    }
+
+    Ok(true)
+}
+
+/// Try parse value as `from(Path, "value")` or `from = "value"`
+fn parse_field_value_for<T>(
+    meta: &ParseNestedMeta<'_>,
+    parser: fn(ParseStream) -> Result<T>,
+) -> Result<(Option<Path>, T)> {
+    if meta.input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in meta.input);
+
+        let path = normalize_path(content.parse()?);
+        content.parse::<Token![,]>()?;
+        let v = parser(&content)?;
+        return Ok((Some(path), v));
+    }
+
+    let v = parser(meta.value()?)?;
+    Ok((None, v))
+}
+
+fn parse_field_value_bool(
+    name: &'static str,
+    meta: &ParseNestedMeta<'_>,
+    field_value: &mut FieldValue<bool>,
+) -> Result<bool> {
+    parse_field_flag(name, meta, field_value, true, |input| {
+        Ok(input.parse::<LitBool>()?.value)
+    })
+}
+
+/// Try parse value as `unwrap`, `unwrap = value`, `unwrap(from)`,
+/// `unwrap(from = value)`, `unwrap(from(Path))` or `unwrap(from(Path, value))`.
+/// Without a value the `default` is used
+fn parse_field_flag<T: Clone>(
+    name: &'static str,
+    meta: &ParseNestedMeta<'_>,
+    field_value: &mut FieldValue<T>,
+    default: T,
+    parser: fn(ParseStream) -> Result<T>,
+) -> Result<bool> {
+    if !meta.path.is_ident(name) {
+        return Ok(false);
+    }
+
+    if meta.input.peek(token::Paren) {
+        meta.parse_nested_meta(|meta| {
+            let is_both = meta.path.is_ident("from_into") || meta.path.is_ident("into_from");
+            let is_from = is_both || meta.path.is_ident("from");
+            let is_into = is_both || meta.path.is_ident("into");
+
+            if !is_from && !is_into {
+                return Err(unknown_error(
+                    &meta.path,
+                    "unknown field",
+                    FIELD_TARGET_KEYS,
+                ));
+            }
+
+            let (path, value) = parse_field_flag_for(&meta, &default, parser)?;
+            if is_from && !field_value.set_from(path.clone(), value.clone()) {
+                return Err(meta.error(format!("duplicate `{name}` for `from`")));
+            }
+            if is_into && !field_value.set_into(path, value) {
+                return Err(meta.error(format!("duplicate `{name}` for `into`")));
+            }
+            Ok(())
+        })?;
+    } else {
+        let value = if meta.input.peek(Token![=]) {
+            parser(meta.value()?)?
+        } else {
+            default
+        };
+        if !field_value.set_common(value) {
+            return Err(meta.error(format!("duplicate `{name}`")));
+        }
+    }
+
+    Ok(true)
+}
+
+fn parse_field_flag_for<T: Clone>(
+    meta: &ParseNestedMeta<'_>,
+    default: &T,
+    parser: fn(ParseStream) -> Result<T>,
+) -> Result<(Option<Path>, T)> {
+    if meta.input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in meta.input);
+
+        let path = normalize_path(content.parse()?);
+        if content.is_empty() {
+            return Ok((Some(path), default.clone()));
+        }
+        content.parse::<Token![,]>()?;
+        return Ok((Some(path), parser(&content)?));
+    }
+
+    if meta.input.peek(Token![=]) {
+        return Ok((None, parser(meta.value()?)?));
+    }
+
+    Ok((None, default.clone()))
+}
+
+/// Try parse value as `map_if(cond = "expr", then = "expr", else = "expr")`,
+/// `map_if(from(cond = "expr", ..))` or `map_if(into(Path, cond = "expr", ..))`
+fn parse_field_map_if(
+    meta: &ParseNestedMeta<'_>,
+    field_value: &mut FieldValue<MapIf>,
+) -> Result<bool> {
+    if !meta.path.is_ident("map_if") {
+        return Ok(false);
+    }
+
+    let mut common = MapIfValues::default();
+    meta.parse_nested_meta(|meta| {
+        let is_both = meta.path.is_ident("from_into") || meta.path.is_ident("into_from");
+        let is_from = is_both || meta.path.is_ident("from");
+        let is_into = is_both || meta.path.is_ident("into");
+
+        if !is_from && !is_into {
+            if common.parse(&meta)? {
+                return Ok(());
+            }
+            return Err(unknown_error(&meta.path, "unknown value", MAP_IF_KEYS));
+        }
+
+        let mut path = None;
+        let mut values = MapIfValues::default();
+        meta.parse_nested_meta(|meta| {
+            if values.parse(&meta)? {
+                return Ok(());
+            }
+            let has_value = !meta.input.is_empty() && !meta.input.peek(Token![,]);
+            if path.is_some() || values.is_set() || has_value {
+                return Err(unknown_error(&meta.path, "unknown value", MAP_IF_KEYS));
+            }
+            path = Some(normalize_path(meta.path.clone()));
+            Ok(())
+        })?;
+
+        let map_if = values.build(&meta)?;
+        if is_from && !field_value.set_from(path.clone(), map_if.clone()) {
+            return Err(meta.error("duplicate `map_if` for `from`"));
+        }
+        if is_into && !field_value.set_into(path, map_if) {
+            return Err(meta.error("duplicate `map_if` for `into`"));
+        }
+        Ok(())
+    })?;
+
+    if common.is_set() && !field_value.set_common(common.build(meta)?) {
+        return Err(meta.error("duplicate `map_if`"));
+    }
+
+    Ok(true)
+}
+
+/// `cond`, `then` and `else` of `map_if`
+#[derive(Default)]
+struct MapIfValues {
+    cond: Option<proc_macro2::Literal>,
+    then: Option<proc_macro2::Literal>,
+    otherwise: Option<proc_macro2::Literal>,
+}
+
+impl MapIfValues {
+    /// Try parse `cond = "expr"`, `then = "expr"` or `else = "expr"`
+    fn parse(&mut self, meta: &ParseNestedMeta<'_>) -> Result<bool> {
+        let value = if meta.path.is_ident("cond") {
+            &mut self.cond
+        } else if meta.path.is_ident("then") {
+            &mut self.then
+        } else if meta.path.is_ident("else") {
+            &mut self.otherwise
+        } else {
+            return Ok(false);
+        };
+        if value.replace(meta.value()?.parse()?).is_some() {
+            return Err(meta.error("duplicate value in `map_if`"));
+        }
+        Ok(true)
+    }
+
+    fn is_set(&self) -> bool {
+        self.cond.is_some() || self.then.is_some() || self.otherwise.is_some()
+    }
+
+    fn build(self, meta: &ParseNestedMeta<'_>) -> Result<MapIf> {
+        let missing = |key| meta.error(format!("`map_if` requires `{key}`"));
+        Ok(MapIf {
+            cond: self.cond.ok_or_else(|| missing("cond"))?,
+            then: self.then.ok_or_else(|| missing("then"))?,
+            otherwise: self.otherwise.ok_or_else(|| missing("else"))?,
+        })
+    }
+}
+
+fn parse_unwrap(input: ParseStream) -> Result<Unwrap> {
+    let mode: Ident = input.parse()?;
+    match mode.to_string().as_str() {
+        "default" => Ok(Unwrap::Default),
+        "expect" => Ok(Unwrap::Expect),
+        "required" => Ok(Unwrap::Required),
+        _ => Err(unknown_error(
+            &mode,
+            "unknown unwrap mode",
+            &["default", "expect", "required"],
+        )),
+    }
+}
+
+/// Options which make no sense together for the same conversion
+const CONFLICTS: &[(&str, &str)] = &[
+    ("skip", "skip_if"),
+    ("skip", "map"),
+    ("skip", "map_if"),
+    ("skip", "with"),
+    ("skip", "await_with"),
+    ("skip", "rename"),
+    ("skip", "wrap"),
+    ("skip", "unwrap"),
+    ("map", "map_if"),
+    ("map", "with"),
+    ("map_if", "with"),
+    ("map_if", "await_with"),
+    ("map_if", "wrap"),
+    ("map_if", "unwrap"),
+    ("map", "await_with"),
+    ("with", "await_with"),
+    ("await_with", "wrap"),
+    ("await_with", "unwrap"),
+    ("map", "wrap"),
+    ("map", "unwrap"),
+    ("with", "wrap"),
+    ("with", "unwrap"),
+    ("wrap", "unwrap"),
+    ("none_variant", "skip"),
+    ("none_variant", "map"),
+    ("none_variant", "map_if"),
+    ("none_variant", "with"),
+    ("none_variant", "await_with"),
+    ("none_variant", "wrap"),
+    ("none_variant", "unwrap"),
+];
+
+/// Options which replace the `.into()` of the field value, only one of them can be used
+const VALUE_CONVERSIONS: &[&str] = &[
+    "cast",
+    "saturating",
+    "wrapping",
+    "checked",
+    "display",
+    "parse",
+];
+
+/// Options which replace the value conversion of the field
+const VALUE_CONVERSION_CONFLICTS: &[&str] = &[
+    "skip",
+    "map",
+    "map_if",
+    "with",
+    "await_with",
+    "none_variant",
+    "wrap",
+    "unwrap",
+];
+
+fn check_conflicts(fields: &Fields, from: &[Path], into: &[Path]) -> Result<()> {
+    for (name, params) in fields {
+        let targets = from
+            .iter()
+            .map(|path| ("from", path, params.levels_from(path)))
+            .chain(
+                into.iter()
+                    .map(|path| ("into", path, params.levels_into(path))),
+            );
+
+        for (direction, path, levels) in targets {
+            let level = |option: &str| {
+                levels
+                    .iter()
+                    .find(|(o, _)| *o == option)
+                    .and_then(|(_, level)| *level)
+            };
+
+            let conversions = VALUE_CONVERSIONS.iter().enumerate().flat_map(|(i, a)| {
+                (VALUE_CONVERSIONS[i + 1..].iter())
+                    .chain(VALUE_CONVERSION_CONFLICTS)
+                    .map(move |b| (*a, *b))
+            });
+            for (a, b) in CONFLICTS.iter().copied().chain(conversions) {
+                // a more specific value overrides a common one, so it is not a conflict
+                let a_level = level(a);
+                if a_level.is_none() || a_level != level(b) {
+                    continue;
+                }
+
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "`{a}` conflicts with `{b}` for field `{name}` in `{direction}({})`",
+                        path_to_string(path),
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Field specialisations like `rename(from(Path, value))`
+/// must name a path declared on the container, otherwise they are never used
+fn check_undeclared_targets(fields: &Fields, from: &[Path], into: &[Path]) -> Result<()> {
+    for (name, params) in fields {
+        let (field_from, field_into) = params.specialised_paths();
+
+        for (direction, declared, paths) in [("from", from, field_from), ("into", into, field_into)]
+        {
+            for path in paths {
+                if declared.contains(path) {
+                    continue;
+                }
+
+                let path_str = path_to_string(path);
+                let mut message = format!(
+                    "field `{name}` refers to `{direction}({path_str})`, \
+                     which is not declared on the container"
+                );
+
+                // e.g. `Cat` for the declared `other::Cat`
+                let last = path.segments.last().map(|s| &s.ident);
+                let similar = declared
+                    .iter()
+                    .find(|d| d.segments.last().map(|s| &s.ident) == last);
+                if let Some(similar) = similar {
+                    message += &format!(", did you mean `{}`?", path_to_string(similar));
+                }
+
+                return Err(syn::Error::new_spanned(path, message));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Paths are compared syntactically, so `self::Cat` is reduced to `Cat`
+fn normalize_path(mut path: Path) -> Path {
+    if path.leading_colon.is_none()
+        && path.segments.len() > 1
+        && path.segments.first().is_some_and(|s| s.ident == "self")
+    {
+        path.segments = path.segments.into_iter().skip(1).collect();
+    }
+    path
+}
+
+/// Error for an unknown key listing the valid ones and the closest match
+fn unknown_error(found: &impl quote::ToTokens, message: &str, expected: &[&str]) -> syn::Error {
+    let found_str = found.to_token_stream().to_string();
+
+    let closest = expected
+        .iter()
+        .map(|e| (edit_distance(&found_str, e), e))
+        .filter(|(distance, _)| *distance <= 2 && *distance < found_str.len())
+        .min_by_key(|(distance, _)| *distance);
+
+    let expected = expected
+        .iter()
+        .map(|e| format!("`{e}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let message = match closest {
+        Some((_, closest)) => {
+            format!(
+                "{message} `{found_str}`, did you mean `{closest}`? expected one of: {expected}"
+            )
+        }
+        None => format!("{message} `{found_str}`, expected one of: {expected}"),
+    };
+
+    syn::Error::new_spanned(found, message)
+}
+
+/// Levenshtein distance between two strings
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            current.push(substitution.min(prev[j + 1] + 1).min(current[j] + 1));
+        }
+        prev = current;
+    }
+
+    prev[b.len()]
+}
+
+fn detect_type_for_serviced(ty: &syn::Type, recurse: u64) -> AdditionalType {
+    let syn::Type::Path(syn::TypePath {
+        path: Path { segments, .. },
+        ..
+    }) = &ty
+    else {
+        return AdditionalType::None;
+    };
+
+    match segments.iter().next() {
+        Some(seg) if seg.ident == "Option" => match is_support_type_for_serviced(seg, recurse) {
+            (true, AdditionalType::None) => return AdditionalType::Option,
+            (true, AdditionalType::Vec) => return AdditionalType::OptionVec,
+            _ => (),
+        },
+        Some(seg) if seg.ident == "Vec" => match is_support_type_for_serviced(seg, recurse) {
+            (true, AdditionalType::None) => return AdditionalType::Vec,
+            (true, AdditionalType::Option) => return AdditionalType::VecOption,
+            _ => (),
+        },
+        _ => (),
+    }
+
+    AdditionalType::None
+}
+
+fn is_support_type_for_serviced(seg: &PathSegment, recurse: u64) -> (bool, AdditionalType) {
+    if let syn::PathArguments::AngleBracketed(a) = &seg.arguments {
+        let a_next = a.args.iter().next();
+        if let Some(syn::GenericArgument::Type(ty)) = a_next {
+            if recurse < 1 {
+                return (true, detect_type_for_serviced(ty, 1));
+            }
+            return (true, AdditionalType::None);
+        }
+    };
+    (false, AdditionalType::None)
+}
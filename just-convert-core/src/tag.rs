@@ -0,0 +1,167 @@
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::Result;
+
+use crate::{
+    enums::{captures, name_pattern, other_variant, parse_names, variant_name},
+    path_to_string, PathParams, VariantParams, Variants,
+};
+
+/// `From<Enum> for Struct`, the variant name is stored in the `tag` field
+/// and the variant fields in the `Option` fields, `None` for the fields of other variants
+pub(crate) fn build_into_tagged(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    let ty = &target.path;
+    let tag = tag_ident(target);
+    let columns = columns(variants, target)?;
+
+    let arms = variants.iter().map(|(variant, params)| {
+        let nones = columns.iter().map(|column| quote! { #column: None, });
+        if captures(params) {
+            return quote! {
+                #current::#variant(value) => #ty { #tag: value.into(), #(#nones)* },
+            };
+        }
+
+        let name = variant_name(variant, params, target);
+        let bindings = bindings(params);
+        let pattern = variant_pattern(variant, params, &bindings);
+        let values = columns.iter().map(|column| {
+            let field = params
+                .tag_fields
+                .iter()
+                .position(|f| f.as_ref() == Some(column));
+            match field.map(|i| &bindings[i]) {
+                Some(binding) => quote! { #column: Some(#binding.into()), },
+                None => quote! { #column: None, },
+            }
+        });
+        quote! {
+            #current::#pattern => #ty { #tag: #name.into(), #(#values)* },
+        }
+    });
+
+    Ok(quote! {
+        impl std::convert::From<#current> for #ty {
+            fn from(value: #current) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// `TryFrom<Struct> for Enum`, the variant is chosen by the `tag` field,
+/// its fields must be `Some`
+pub(crate) fn build_from_tagged(
+    current: &Ident,
+    target: &PathParams,
+    variants: &Variants,
+) -> Result<TokenStream> {
+    let ty = &target.path;
+    let tag = tag_ident(target);
+    columns(variants, target)?;
+
+    let arms = parse_names(variants, target)?
+        .into_iter()
+        .map(|(variant, params, names)| {
+            let pattern = name_pattern(&names, target);
+            let values = params.tag_fields.iter().flatten().map(|column| {
+                let message = format!("missing `{column}` for `{tag}` `{}`", names[0]);
+                quote! { this.#column.ok_or(#message)?.into() }
+            });
+            let value = match &params.fields {
+                syn::Fields::Unit => quote! { #current::#variant },
+                syn::Fields::Unnamed(_) => quote! { #current::#variant(#(#values),*) },
+                syn::Fields::Named(fields) => {
+                    let fields = fields.named.iter().filter_map(|field| field.ident.as_ref());
+                    quote! { #current::#variant { #(#fields: #values),* } }
+                }
+            };
+            quote! { #pattern => Ok(#value), }
+        });
+
+    let name = current.to_string();
+    let krate = &target.krate;
+    let other = match other_variant(variants, target) {
+        Some((other, params)) if captures(params) => {
+            quote! { value => Ok(#current::#other(value.into())), }
+        }
+        Some((other, _)) => quote! { _ => Ok(#current::#other), },
+        None => quote! {
+            value => Err(#krate::UnknownVariant::new(#name, value).into()),
+        },
+    };
+
+    Ok(quote! {
+        impl std::convert::TryFrom<#ty> for #current {
+            type Error = Box<dyn std::error::Error + Send + Sync>;
+
+            fn try_from(this: #ty) -> Result<Self, Self::Error> {
+                match &*this.#tag {
+                    #(#arms)*
+                    #other
+                }
+            }
+        }
+    })
+}
+
+fn tag_ident(target: &PathParams) -> &Ident {
+    target
+        .tag
+        .as_ref()
+        .expect("tagged conversions have the `tag`")
+}
+
+/// Fields of the struct holding the variant fields, in order of the first use
+fn columns<'a>(variants: &'a Variants, target: &PathParams) -> Result<Vec<&'a Ident>> {
+    let tag = tag_ident(target);
+    let mut columns: Vec<&Ident> = vec![];
+    for (variant, params) in variants.iter().filter(|(_, params)| !captures(params)) {
+        let mut variant_columns: Vec<&Ident> = vec![];
+        for (i, column) in params.tag_fields.iter().enumerate() {
+            let Some(column) = column else {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!(
+                        "field {i} of the variant `{variant}` needs `#[convert(rename = name)]` \
+                         to be stored in a field of `{}`",
+                        path_to_string(&target.path)
+                    ),
+                ));
+            };
+            if column == tag || variant_columns.contains(&column) {
+                return Err(syn::Error::new(
+                    column.span(),
+                    format!("the field `{column}` is already used in the variant `{variant}`"),
+                ));
+            }
+            variant_columns.push(column);
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+    }
+    Ok(columns)
+}
+
+/// Locals for the variant fields in the match pattern
+fn bindings(params: &VariantParams) -> Vec<Ident> {
+    (params.fields.iter().enumerate())
+        .map(|(i, field)| field.ident.clone().unwrap_or_else(|| format_ident!("v{i}")))
+        .collect()
+}
+
+/// `Variant`, `Variant(v0, v1)` or `Variant { a, b }`
+fn variant_pattern(variant: &Ident, params: &VariantParams, bindings: &[Ident]) -> TokenStream {
+    match &params.fields {
+        syn::Fields::Unit => quote! { #variant },
+        syn::Fields::Unnamed(_) => quote! { #variant(#(#bindings),*) },
+        syn::Fields::Named(_) => quote! { #variant { #(#bindings),* } },
+    }
+}
@@ -0,0 +1,137 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Path, Result};
+
+use crate::{
+    assign::{build_literal, needs_binding, source_fields},
+    build::{build_from_assigns, build_into_assigns, build_value},
+    path_to_string, Params, PathParams, VariantShape,
+};
+
+impl Params {
+    /// `From<Struct> for Enum`, or `TryFrom` when fallible, wrapping the struct into the variant
+    pub(crate) fn build_into_variant(
+        &self,
+        into: &PathParams,
+        shape: VariantShape,
+    ) -> Result<TokenStream> {
+        let current = &self.name;
+        let variant = &into.path;
+        let enum_path = enum_path(variant);
+
+        let value = match shape {
+            VariantShape::Tuple => quote! { #variant(this) },
+            VariantShape::Struct => {
+                let values = build_into_assigns(into, current, self.fields.clone())?;
+                let bind = needs_binding(&values);
+                let assigns = values
+                    .iter()
+                    .filter_map(|assign| {
+                        let value = assign.value_or(quote!(Default::default()))?;
+                        Some((assign, value))
+                    })
+                    .collect();
+                build_literal(variant, assigns, bind, quote!())
+            }
+        };
+        let body = build_value(into, value);
+
+        if into.fallible {
+            return Ok(quote! {
+                impl std::convert::TryFrom<#current> for #enum_path {
+                    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+                    fn try_from(this: #current) -> Result<Self, Self::Error> {
+                        #body
+                    }
+                }
+            });
+        }
+
+        Ok(quote! {
+            impl std::convert::From<#current> for #enum_path {
+                fn from(this: #current) -> Self {
+                    #body
+                }
+            }
+        })
+    }
+
+    /// `TryFrom<Enum> for Struct`, failing for the other variants
+    pub(crate) fn build_from_variant(
+        &self,
+        from: &PathParams,
+        shape: VariantShape,
+    ) -> Result<TokenStream> {
+        let current = &self.name;
+        let variant = &from.path;
+        let enum_path = enum_path(variant);
+        let message = format!("expected the `{}` variant", path_to_string(variant));
+
+        let body = match shape {
+            VariantShape::Tuple => {
+                let value = build_value(from, quote!(this));
+                quote! {
+                    let this = match value {
+                        #variant(this) => this,
+                        _ => return Err(#message.into()),
+                    };
+                    #value
+                }
+            }
+            VariantShape::Struct => {
+                let values = build_from_assigns(from, current, self.fields.clone())?;
+                let bind = needs_binding(&values);
+                let assigns = values
+                    .iter()
+                    .filter_map(|assign| match assign.value_or(quote!(Default::default())) {
+                        Some(value) => Some((assign, value)),
+                        None if from.default => None,
+                        None => Some((assign, quote!(Default::default()))),
+                    })
+                    .collect();
+                let default_expr = if from.default {
+                    quote! { ..Default::default() }
+                } else {
+                    quote!()
+                };
+                let value = build_value(from, build_literal(current, assigns, bind, default_expr));
+
+                // the fields of the variant are moved into a struct,
+                // so the values read them as `this.field` like for any other source
+                let fields = source_fields(&values);
+                let types = (0..fields.len()).map(|i| format_ident!("T{i}"));
+                let types2 = types.clone();
+                quote! {
+                    struct VariantFields<#(#types),*> {
+                        #(#fields: #types2,)*
+                    }
+
+                    let this = match value {
+                        #variant { #(#fields,)* .. } => VariantFields { #(#fields,)* },
+                        _ => return Err(#message.into()),
+                    };
+                    #value
+                }
+            }
+        };
+
+        Ok(quote! {
+            impl std::convert::TryFrom<#enum_path> for #current {
+                type Error = Box<dyn std::error::Error + Send + Sync>;
+
+                fn try_from(value: #enum_path) -> Result<Self, Self::Error> {
+                    #body
+                }
+            }
+        })
+    }
+}
+
+/// `Event` for the `Event::Created` variant
+fn enum_path(variant: &Path) -> Path {
+    let mut path = variant.clone();
+    path.segments.pop();
+    path.segments.pop_punct();
+    path
+}
@@ -0,0 +1,449 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Pretty-printed expansion of the derive
+fn expand(input: TokenStream) -> String {
+    let output = just_convert_core::expand(input);
+    match syn::parse2(output.clone()) {
+        Ok(file) => prettyplease::unparse(&file),
+        Err(_) => output.to_string(),
+    }
+}
+
+#[test]
+fn from() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        struct A {
+            id: i64,
+            name: String,
+        }
+    }));
+}
+
+#[test]
+fn into() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(into(B))]
+        struct A {
+            id: i64,
+            name: String,
+        }
+    }));
+}
+
+#[test]
+fn from_into_default() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(other::B, default))]
+        struct A {
+            id: i64,
+        }
+    }));
+}
+
+#[test]
+fn rename() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            #[convert(rename = user_id)]
+            id: i64,
+        }
+    }));
+}
+
+#[test]
+fn map() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            #[convert(map(from = ". as i64", into = ". as u64"))]
+            age: i64,
+            #[convert(map = "format!(\"{} years\", this.age)")]
+            message: String,
+        }
+    }));
+}
+
+#[test]
+fn skip() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        #[convert(from(C, default))]
+        struct A {
+            id: i64,
+            #[convert(skip)]
+            age: i64,
+        }
+    }));
+}
+
+#[test]
+fn containers() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            value: Option<Value>,
+            items: Vec<Value>,
+            maybe_items: Option<Vec<Value>>,
+            items_maybe: Vec<Option<Value>>,
+        }
+    }));
+}
+
+#[test]
+fn wrap_unwrap() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            #[convert(wrap(from), unwrap(into))]
+            age: Option<i64>,
+        }
+    }));
+}
+
+#[test]
+fn wrap_option_unwrap_option() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B, wrap_option))]
+        #[convert(into(B, unwrap_option))]
+        struct A {
+            id: Option<i64>,
+            items: Vec<i64>,
+        }
+    }));
+}
+
+#[test]
+fn unwrap_modes() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        #[convert(try_from(C))]
+        struct A {
+            #[convert(unwrap(from(B, expect), from(C, required)))]
+            id: i64,
+            #[convert(unwrap = default)]
+            name: String,
+            #[convert(unwrap_or = "\"guest\".to_string()")]
+            role: String,
+        }
+    }));
+}
+
+#[test]
+fn fallible() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(try_from_into(B))]
+        struct A {
+            #[convert(unwrap(from = required))]
+            id: i64,
+        }
+    }));
+}
+
+#[test]
+fn specialize() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        #[convert(from_into(self::C))]
+        struct A {
+            #[convert(rename(from_into(C, c_id)))]
+            #[convert(rename(from = user_id))]
+            id: String,
+            #[convert(skip(into))]
+            age: i64,
+        }
+    }));
+}
+
+#[test]
+fn generate() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(generate(UserDto, derive(Debug, Clone), vis = pub(crate)))]
+        struct User {
+            id: i64,
+            #[convert(rename = user_name)]
+            name: String,
+            #[convert(skip)]
+            password: String,
+            #[convert(ty = Option<String>)]
+            email: Option<Email>,
+        }
+    }));
+}
+
+#[test]
+fn patch() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(patch(UserPatch, derive(Default)))]
+        struct User {
+            #[convert(skip)]
+            id: i64,
+            #[convert(rename = user_name)]
+            name: String,
+            #[convert(map(from(UserPatch, ".to_lowercase()")))]
+            email: String,
+            phone: Option<String>,
+        }
+    }));
+}
+
+#[test]
+fn error() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        struct A {
+            #[convert(renam = user_id)]
+            id: i64,
+        }
+    }));
+}
+
+#[test]
+fn base() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(into(Record, base))]
+        #[convert(from(Record, base))]
+        struct A {
+            name: String,
+            #[convert(skip)]
+            loaded_at: u64,
+        }
+    }));
+}
+
+#[test]
+fn with_base() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(Record, with_base))]
+        struct Entity {
+            name: String,
+            #[convert(skip)]
+            loaded_at: u64,
+        }
+    }));
+}
+
+#[test]
+fn context() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B, context = Ctx))]
+        #[convert(into(B))]
+        struct A {
+            #[convert(map(from = "ctx.next_id()"))]
+            id: i64,
+            #[convert(with = to_url)]
+            url: String,
+        }
+    }));
+}
+
+#[test]
+fn async_from() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(async_from(B))]
+        struct A {
+            id: i64,
+            #[convert(rename = author_id, await_with = find_user_name)]
+            author: String,
+        }
+    }));
+}
+
+#[test]
+fn hooks() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B, after = normalize))]
+        #[convert(into(C, after = normalize_c, validate = validate_c))]
+        struct A {
+            email: String,
+        }
+    }));
+}
+
+#[test]
+fn conditional() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            #[convert(skip_if(from = "this.is_private"))]
+            email: String,
+            #[convert(map_if(
+                from(cond = ". < 0", then = "0", else = ". as u32"),
+                into(cond = ". > 150", then = "150", else = ". as i64"),
+            ))]
+            age: u32,
+        }
+    }));
+}
+
+#[test]
+fn partial_move() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(B))]
+        struct A {
+            first: String,
+            last: String,
+            #[convert(map = "format!(\"{} {}\", this.first, this.last)")]
+            full_name: String,
+        }
+    }));
+}
+
+#[test]
+fn partial_move_order() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        struct A {
+            first: String,
+            #[convert(with = parse_code)]
+            code: u32,
+            #[convert(map = "this.first.len()")]
+            first_len: usize,
+        }
+    }));
+}
+
+#[test]
+fn out_fields() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(B))]
+        struct A {
+            #[convert(map = "format!(\"{} {}\", out.first, out.last)")]
+            display_name: String,
+            #[convert(map = ".trim().to_string()")]
+            first: String,
+            last: String,
+        }
+    }));
+}
+
+#[test]
+fn enum_integer() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from(i32), into(i32))]
+        enum Status {
+            Active = 1,
+            Blocked,
+            #[convert(value = 10)]
+            Deleted,
+        }
+    }));
+}
+
+#[test]
+fn enum_string() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(String, rename_all = "snake_case"))]
+        enum Status {
+            Active,
+            OnHold,
+            #[convert(rename = "removed", alias("deleted", "archived"))]
+            Deleted,
+        }
+    }));
+}
+
+#[test]
+fn enum_other() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(external::Level))]
+        enum Level {
+            Low,
+            Named { value: i32 },
+            Tuple(i32, String),
+            #[convert(other)]
+            Other(external::Level),
+        }
+    }));
+}
+
+#[test]
+fn enum_variants() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(external::JobState))]
+        enum JobState {
+            #[convert(from_into(external::JobState, variants(Pending, Queued), canonical = Queued))]
+            Waiting,
+            #[convert(from(external::JobState, variants(Succeeded)))]
+            Done(String),
+        }
+    }));
+}
+
+#[test]
+fn enum_variant() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(into(Event::Created))]
+        #[convert(into(Change::Renamed, fields))]
+        struct Created {
+            id: u64,
+            #[convert(rename = new_name)]
+            name: String,
+        }
+    }));
+}
+
+#[test]
+fn enum_tag() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(ShapeRow, tag = kind, rename_all = "snake_case"))]
+        enum Shape {
+            Circle { radius: f64 },
+            Square(#[convert(rename = width)] f64),
+            Empty,
+        }
+    }));
+}
+
+#[test]
+fn numeric() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(try_from(B), into(B))]
+        struct A {
+            #[convert(checked(from))]
+            id: u32,
+            #[convert(saturating)]
+            level: Option<i32>,
+            #[convert(checked(from), wrapping(into))]
+            readings: Vec<u8>,
+        }
+    }));
+}
+
+#[test]
+fn display_parse() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(try_from(B), into(B))]
+        struct A {
+            #[convert(parse(from), display(into))]
+            id: u64,
+            #[convert(parse(from), display(into))]
+            tags: Option<Vec<Tag>>,
+        }
+    }));
+}
+
+#[test]
+fn crate_path() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(crate = ::my_utils::convert)]
+        #[convert(from(B), into(B))]
+        struct A {
+            id: i64,
+        }
+    }));
+}
+
+#[test]
+fn enum_none() {
+    insta::assert_snapshot!(expand(quote! {
+        #[convert(from_into(Status))]
+        enum ProtoStatus {
+            #[convert(none)]
+            Unspecified,
+            Active,
+        }
+    }));
+}
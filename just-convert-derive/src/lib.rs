@@ -0,0 +1,13 @@
+//! Derive macro of the just-convert crate.
+//!
+//! Use it through the `just-convert` crate, which also provides
+//! the traits the generated code refers to.
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+#[proc_macro_derive(JustConvert, attributes(convert))]
+pub fn just_convert_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    just_convert_core::build_impl(input).into()
+}
@@ -1,6 +1,6 @@
 //! just-convert make easier to convert between structs.
 //!
-//! This crate provides JustConvert derive macro.
+//! This crate provides JustConvert derive macro and the traits it implements.
 //!
 //! Example of use:
 //!
@@ -33,138 +33,86 @@
 //!
 //! See more [examples](https://github.com/vettich/just-convert-rs/tree/main/examples)
 
-use std::collections::HashMap;
+pub use just_convert_derive::JustConvert;
+pub use num::{NumConvert, OutOfRange};
 
-use parse::parse_params;
-use proc_macro::TokenStream;
-use syn::{parse_macro_input, DeriveInput, Ident, Path};
+mod num;
 
-mod build;
-mod map;
-mod parse;
-
-#[proc_macro_derive(JustConvert, attributes(convert))]
-pub fn just_convert_derive(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    build_impl(input).into()
+/// Update the value in place from `T`, keeping the fields `T` does not carry.
+///
+/// Implemented by the derive for each plain `from(T)` or `from_into(T)` conversion,
+/// not for `try_from`, `async_from` or the ones with a `context`.
+pub trait UpdateFrom<T> {
+    fn update_from(&mut self, src: T);
 }
 
-fn build_impl(input: DeriveInput) -> proc_macro2::TokenStream {
-    let params = match parse_params(&input) {
-        Ok(p) => p,
-        Err(err) => return err.to_compile_error(),
-    };
-
-    params
-        .build()
-        .unwrap_or_else(syn::Error::into_compile_error)
+/// Build `Self` from `T`, taking the fields `T` does not carry from `base`.
+///
+/// Implemented by the derive for each `from(T, with_base)` or `from(T, base)` conversion.
+pub trait FromWithBase<T>: Sized {
+    fn from_with_base(src: T, base: Self) -> Self;
 }
 
-#[derive(Debug)]
-struct Params {
-    name: Ident,
-    from: Vec<PathParams>,
-    into: Vec<PathParams>,
-    fields: Fields,
+/// Convert into `T`, taking the fields `Self` does not carry from `base`.
+///
+/// Implemented by the derive for each `into(T, with_base)` or `into(T, base)` conversion.
+pub trait IntoWithBase<T> {
+    fn into_with_base(self, base: T) -> T;
 }
 
-#[derive(Debug)]
-struct PathParams {
-    path: Path,
-    default: bool,
-    wrap_option: bool,
+/// Build `Self` from `T` with an extra context value (locale, base URL, etc.).
+///
+/// Implemented by the derive for each `from(T, context = C)` conversion.
+pub trait FromWithContext<T, C> {
+    fn from_with_context(src: T, ctx: &C) -> Self;
 }
 
-#[derive(Debug, Clone)]
-struct FieldParams {
-    map: FieldValue<proc_macro2::Literal>,
-    rename: FieldValue<Ident>,
-    wrap: FieldValue<bool>,
-    unwrap: FieldValue<bool>,
-    skip: FieldValue<bool>,
-    a_type: AdditionalType,
+/// Convert into `T` with an extra context value.
+///
+/// Implemented by the derive for each `into(T, context = C)` conversion.
+pub trait IntoWithContext<T, C> {
+    fn into_with_context(self, ctx: &C) -> T;
 }
 
-impl FieldParams {
-    fn new() -> Self {
-        Self {
-            map: FieldValue::new(),
-            rename: FieldValue::new(),
-            wrap: FieldValue::new(),
-            unwrap: FieldValue::new(),
-            skip: FieldValue::new(),
-            a_type: AdditionalType::None,
-        }
-    }
+/// Build `Self` from `T` asynchronously.
+///
+/// Implemented by the derive for each `async_from(T)` conversion,
+/// fields marked with `await_with = path` are resolved with `path(value).await`.
+/// The future is `Send`, so it can be spawned on a multi-threaded runtime.
+pub trait AsyncFrom<T>: Sized {
+    fn async_from(src: T) -> impl std::future::Future<Output = Self> + Send;
 }
 
-#[derive(Debug, Default, Clone)]
-struct FieldValue<T> {
-    common: Option<T>,
-    common_from: Option<T>,
-    common_into: Option<T>,
-    from: HashMap<Path, T>,
-    into: HashMap<Path, T>,
+/// Error of the conversions into an enum from a value which matches no variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+    enum_name: &'static str,
+    value: String,
 }
 
-impl<T> FieldValue<T> {
-    fn new() -> Self {
+impl UnknownVariant {
+    pub fn new(enum_name: &'static str, value: impl std::fmt::Display) -> Self {
         Self {
-            common: None,
-            common_from: None,
-            common_into: None,
-            from: [].into(),
-            into: [].into(),
+            enum_name,
+            value: value.to_string(),
         }
     }
 
-    fn set_from(&mut self, path: Option<Path>, value: T) {
-        if let Some(path) = path {
-            self.from.insert(path, value);
-        } else {
-            self.common_from = Some(value);
-        }
+    /// Name of the enum
+    pub fn enum_name(&self) -> &'static str {
+        self.enum_name
     }
 
-    fn set_into(&mut self, path: Option<Path>, value: T) {
-        if let Some(path) = path {
-            self.into.insert(path, value);
-        } else {
-            self.common_into = Some(value);
-        }
+    /// The value which matches no variant
+    pub fn value(&self) -> &str {
+        &self.value
     }
 }
 
-#[derive(Debug, Default, Clone, Copy)]
-enum AdditionalType {
-    #[default]
-    None,
-    /// Option<T>
-    Option,
-    /// Option<Vec<T>>
-    OptionVec,
-    /// Vec<T>
-    Vec,
-    /// Vec<Option<T>>
-    VecOption,
-}
-
-impl AdditionalType {
-    fn is_option(self) -> bool {
-        matches!(self, Self::Option)
-    }
-
-    fn is_option_vec(self) -> bool {
-        matches!(self, Self::OptionVec)
-    }
-
-    fn is_vec(self) -> bool {
-        matches!(self, Self::Vec)
-    }
-
-    fn is_vec_option(self) -> bool {
-        matches!(self, Self::VecOption)
+impl std::fmt::Display for UnknownVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown `{}` value `{}`", self.enum_name, self.value)
     }
 }
 
-type Fields = HashMap<Ident, FieldParams>;
+impl std::error::Error for UnknownVariant {}
@@ -0,0 +1,199 @@
+//! Numeric conversions behind the `cast`, `saturating`, `wrapping` and `checked` field options.
+
+/// Convert a primitive number into `T` with an explicit overflow policy.
+///
+/// Implemented for every pair of the primitive integer and float types.
+pub trait NumConvert<T> {
+    /// Same as `as`
+    fn cast(self) -> T;
+
+    /// Values out of the range of `T` are clamped to its bounds
+    fn saturating(self) -> T;
+
+    /// Integers wrap around the bounds of `T`, floats are converted like [`cast`](Self::cast)
+    fn wrapping(self) -> T;
+
+    /// Fails for the values out of the range of `T`, the fraction of floats is truncated
+    fn checked(self) -> Result<T, OutOfRange>;
+}
+
+/// Error of the `checked` conversions of a value out of the range of the target type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutOfRange {
+    value: String,
+    target: &'static str,
+    field: Option<String>,
+}
+
+impl OutOfRange {
+    pub fn new(value: impl std::fmt::Display, target: &'static str) -> Self {
+        Self {
+            value: value.to_string(),
+            target,
+            field: None,
+        }
+    }
+
+    /// Set the path of the converted field, e.g. `scores[2]`
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// The value out of the range
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Name of the target type
+    pub fn target(&self) -> &'static str {
+        self.target
+    }
+
+    /// Path of the converted field, if any
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+}
+
+impl std::fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value `{}`", self.value)?;
+        if let Some(field) = &self.field {
+            write!(f, " of field `{field}`")?;
+        }
+        write!(f, " is out of range of `{}`", self.target)
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+macro_rules! impl_num_convert {
+    ($kind:ident: [$($from:ty),*] => $to:tt) => {
+        $(impl_num_convert!(@each $kind, $from => $to);)*
+    };
+    (@each $kind:ident, $from:ty => [$($to:ty),*]) => {
+        $(impl_num_convert!(@impl $kind, $from => $to);)*
+    };
+    (@impl int_to_int, $from:ty => $to:ty) => {
+        #[allow(clippy::useless_conversion, clippy::unnecessary_cast)]
+        impl NumConvert<$to> for $from {
+            fn cast(self) -> $to {
+                self as $to
+            }
+
+            fn saturating(self) -> $to {
+                match <$to>::try_from(self) {
+                    Ok(value) => value,
+                    Err(_) if self > <$from>::default() => <$to>::MAX,
+                    Err(_) => <$to>::MIN,
+                }
+            }
+
+            fn wrapping(self) -> $to {
+                self as $to
+            }
+
+            fn checked(self) -> Result<$to, OutOfRange> {
+                <$to>::try_from(self).map_err(|_| OutOfRange::new(self, stringify!($to)))
+            }
+        }
+    };
+    (@impl int_to_float, $from:ty => $to:ty) => {
+        impl NumConvert<$to> for $from {
+            fn cast(self) -> $to {
+                self as $to
+            }
+
+            fn saturating(self) -> $to {
+                // only `u128` overflows `f32`, and it is never negative
+                (self as $to).min(<$to>::MAX)
+            }
+
+            fn wrapping(self) -> $to {
+                self as $to
+            }
+
+            fn checked(self) -> Result<$to, OutOfRange> {
+                let value = self as $to;
+                if value.is_finite() {
+                    Ok(value)
+                } else {
+                    Err(OutOfRange::new(self, stringify!($to)))
+                }
+            }
+        }
+    };
+    (@impl float_to_int, $from:ty => $to:ty) => {
+        #[allow(clippy::unnecessary_cast)]
+        impl NumConvert<$to> for $from {
+            fn cast(self) -> $to {
+                self as $to
+            }
+
+            fn saturating(self) -> $to {
+                // `as` saturates floats, `NaN` becomes 0
+                self as $to
+            }
+
+            fn wrapping(self) -> $to {
+                self as $to
+            }
+
+            fn checked(self) -> Result<$to, OutOfRange> {
+                // the bounds are powers of two, so they are exact in `f64`
+                let value = (self as f64).trunc();
+                let min = <$to>::MIN as f64;
+                let max = if min < 0.0 {
+                    -min
+                } else {
+                    2f64.powi(<$to>::BITS as i32)
+                };
+                if value >= min && value < max {
+                    Ok(self as $to)
+                } else {
+                    Err(OutOfRange::new(self, stringify!($to)))
+                }
+            }
+        }
+    };
+    (@impl float_to_float, $from:ty => $to:ty) => {
+        #[allow(clippy::unnecessary_cast)]
+        impl NumConvert<$to> for $from {
+            fn cast(self) -> $to {
+                self as $to
+            }
+
+            fn saturating(self) -> $to {
+                let value = self as $to;
+                match value.is_infinite() && self.is_finite() {
+                    true if self > 0.0 => <$to>::MAX,
+                    true => <$to>::MIN,
+                    false => value,
+                }
+            }
+
+            fn wrapping(self) -> $to {
+                self as $to
+            }
+
+            fn checked(self) -> Result<$to, OutOfRange> {
+                let value = self as $to;
+                if value.is_infinite() && self.is_finite() {
+                    Err(OutOfRange::new(self, stringify!($to)))
+                } else {
+                    Ok(value)
+                }
+            }
+        }
+    };
+}
+
+impl_num_convert!(int_to_int:
+    [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize]
+    => [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize]);
+impl_num_convert!(int_to_float:
+    [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize] => [f32, f64]);
+impl_num_convert!(float_to_int:
+    [f32, f64] => [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize]);
+impl_num_convert!(float_to_float: [f32, f64] => [f32, f64]);
@@ -0,0 +1,13 @@
+use just_convert::JustConvert;
+
+struct B {
+    id: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(async_into(B))]
+struct A {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use just_convert::JustConvert;
+
+async fn find_user_name(_id: i64) -> String {
+    String::new()
+}
+
+struct B {
+    author: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(await_with = find_user_name)]
+    author: String,
+}
+
+fn main() {}
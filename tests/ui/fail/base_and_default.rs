@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(into(B, default, base))]
+struct A {
+    id: i64,
+}
+
+#[derive(Default)]
+struct B {
+    id: i64,
+    name: String,
+}
+
+fn main() {}
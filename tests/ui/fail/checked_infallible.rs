@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct B {
+    id: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(checked)]
+    id: u32,
+}
+
+fn main() {}
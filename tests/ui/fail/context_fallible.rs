@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+struct Ctx;
+
+struct B {
+    id: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(try_from(B, context = Ctx))]
+struct A {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(rename = user_id)]
+    #[convert(rename = uid)]
+    id: i64,
+}
+
+struct B {
+    user_id: i64,
+}
+
+fn main() {}
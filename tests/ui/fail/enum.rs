@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+enum A {
+    First,
+    #[convert(other)]
+    Other(i32, i32),
+}
+
+enum B {
+    First,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(i32))]
+enum Level {
+    Low = 1,
+    #[convert(value = 1)]
+    High,
+}
+
+fn main() {}
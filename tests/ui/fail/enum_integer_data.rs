@@ -0,0 +1,10 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(i32))]
+enum A {
+    First,
+    Second(i32),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(String, case_insensitive))]
+enum A {
+    First,
+    #[convert(alias = "first")]
+    Second,
+}
+
+fn main() {}
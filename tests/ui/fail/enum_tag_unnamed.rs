@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct Row {
+    kind: String,
+    value: Option<i32>,
+}
+
+#[derive(JustConvert)]
+#[convert(into(Row, tag = kind))]
+enum A {
+    First(i32),
+}
+
+fn main() {}
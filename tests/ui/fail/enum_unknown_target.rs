@@ -0,0 +1,10 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(Strin))]
+enum Status {
+    Active,
+    Blocked,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from_into(B))]
+enum A {
+    #[convert(from_into(B, variants(First, Second)))]
+    First,
+}
+
+enum B {
+    First,
+    Second,
+}
+
+fn main() {}
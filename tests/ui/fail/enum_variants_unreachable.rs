@@ -0,0 +1,16 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+enum A {
+    #[convert(from(B, variants(First, Second)))]
+    First,
+    Second,
+}
+
+enum B {
+    First,
+    Second,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from())]
+struct A {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use just_convert::JustConvert;
+
+struct B {
+    name: String,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(ty = String)]
+    name: String,
+    #[convert(map = "Some(this.name)")]
+    nickname: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct B {
+    age: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(map_if(cond = ". < 0", then = "0"))]
+    age: i64,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(map = ". +")]
+    id: i64,
+}
+
+struct B {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use just_convert::JustConvert;
+
+struct B {
+    first: String,
+    greeting: String,
+}
+
+#[derive(JustConvert)]
+#[convert(into(B))]
+struct A {
+    first: String,
+    #[convert(map = "this.first + \"!\"")]
+    greeting: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct B {
+    status: i32,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(none_variant)]
+    status: i32,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use just_convert::JustConvert;
+
+#[derive(Debug)]
+enum Status {
+    Active,
+}
+
+struct B {
+    statuses: Option<Vec<Status>>,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(none_variant)]
+    statuses: Option<Vec<Status>>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use just_convert::JustConvert;
+
+struct B {
+    a: String,
+    b: String,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(map = "out.b.clone()")]
+    a: String,
+    #[convert(map = "out.a.clone()")]
+    b: String,
+}
+
+fn main() {}
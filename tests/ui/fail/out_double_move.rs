@@ -0,0 +1,15 @@
+use just_convert::JustConvert;
+
+struct B {
+    first: String,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    first: String,
+    #[convert(map = "out.first")]
+    alias: String,
+}
+
+fn main() {}
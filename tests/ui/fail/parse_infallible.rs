@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct B {
+    id: String,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(parse)]
+    id: u64,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use just_convert::JustConvert;
+
+mod other {
+    pub struct Cat {
+        pub cat_id: i64,
+    }
+}
+
+#[derive(JustConvert)]
+#[convert(from(other::Cat))]
+struct A {
+    #[convert(rename(from(Cat, cat_id)))]
+    id: i64,
+}
+
+fn main() {}
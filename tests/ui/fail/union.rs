@@ -0,0 +1,13 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+union A {
+    id: i64,
+}
+
+struct B {
+    id: i64,
+}
+
+fn main() {}
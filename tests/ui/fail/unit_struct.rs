@@ -0,0 +1,9 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A;
+
+struct B;
+
+fn main() {}
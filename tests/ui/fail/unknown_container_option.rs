@@ -0,0 +1,13 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B, wrap_optoin))]
+struct A {
+    id: Option<i64>,
+}
+
+struct B {
+    id: i64,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(renam = user_id)]
+    id: i64,
+}
+
+struct B {
+    user_id: i64,
+}
+
+fn main() {}
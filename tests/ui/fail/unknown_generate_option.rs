@@ -0,0 +1,9 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(generate(UserDto, derives(Debug)))]
+struct User {
+    id: i64,
+}
+
+fn main() {}
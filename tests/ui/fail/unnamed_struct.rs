@@ -0,0 +1,9 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A(i64);
+
+struct B(i64);
+
+fn main() {}
@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+struct B {
+    name: Option<String>,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(unwrap_or = ".unwrap_or_default()")]
+    name: String,
+}
+
+fn main() {}
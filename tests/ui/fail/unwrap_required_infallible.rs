@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(unwrap = required)]
+    id: i64,
+}
+
+struct B {
+    id: Option<i64>,
+}
+
+fn main() {}
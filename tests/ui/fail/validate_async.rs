@@ -0,0 +1,17 @@
+use just_convert::JustConvert;
+
+struct B {
+    id: i64,
+}
+
+fn check(_: &A) -> Result<(), String> {
+    Ok(())
+}
+
+#[derive(JustConvert)]
+#[convert(async_from(B, validate = check))]
+struct A {
+    id: i64,
+}
+
+fn main() {}
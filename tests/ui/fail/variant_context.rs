@@ -0,0 +1,13 @@
+use just_convert::JustConvert;
+
+enum Event {
+    Created(A),
+}
+
+#[derive(JustConvert)]
+#[convert(into(Event::Created, context = u32))]
+struct A {
+    id: u64,
+}
+
+fn main() {}
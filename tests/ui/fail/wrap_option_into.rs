@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(into(B))]
+struct A {
+    #[convert(wrap)]
+    id: Option<i64>,
+}
+
+struct B {
+    id: Option<i64>,
+}
+
+fn main() {}
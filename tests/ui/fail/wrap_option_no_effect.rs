@@ -0,0 +1,14 @@
+use just_convert::JustConvert;
+
+// A has no plain fields which could be wrapped into B
+#[derive(JustConvert)]
+#[convert(from_into(B, wrap_option))]
+struct A {
+    id: Option<i64>,
+}
+
+struct B {
+    id: i64,
+}
+
+fn main() {}
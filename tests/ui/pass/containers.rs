@@ -0,0 +1,44 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from_into(B))]
+struct A {
+    value: Option<ValueA>,
+    items: Vec<ValueA>,
+    maybe_items: Option<Vec<ValueA>>,
+    items_maybe: Vec<Option<ValueA>>,
+}
+
+struct B {
+    value: Option<ValueB>,
+    items: Vec<ValueB>,
+    maybe_items: Option<Vec<ValueB>>,
+    items_maybe: Vec<Option<ValueB>>,
+}
+
+struct ValueA;
+struct ValueB;
+
+impl From<ValueA> for ValueB {
+    fn from(_: ValueA) -> Self {
+        Self
+    }
+}
+
+impl From<ValueB> for ValueA {
+    fn from(_: ValueB) -> Self {
+        Self
+    }
+}
+
+fn main() {
+    let a: A = B {
+        value: Some(ValueB),
+        items: vec![ValueB],
+        maybe_items: None,
+        items_maybe: vec![None, Some(ValueB)],
+    }
+    .into();
+    let b: B = a.into();
+    assert_eq!(b.items_maybe.len(), 2);
+}
@@ -0,0 +1,31 @@
+mod utils {
+    pub mod convert {
+        pub use just_convert::*;
+    }
+}
+
+use utils::convert::{JustConvert, UpdateFrom};
+
+struct B {
+    id: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(crate = crate::utils::convert)]
+#[convert(from(i32))]
+enum Level {
+    Low = 1,
+}
+
+#[derive(JustConvert)]
+#[convert(from(B), crate = crate::utils::convert)]
+struct A {
+    id: i64,
+}
+
+fn main() {
+    let mut a = A::from(B { id: 1 });
+    a.update_from(B { id: 2 });
+    assert_eq!(2, a.id);
+    assert!(Level::try_from(2).is_err());
+}
@@ -0,0 +1,18 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(try_from_into(B))]
+struct A {
+    #[convert(unwrap(from = required))]
+    id: i64,
+}
+
+struct B {
+    id: Option<i64>,
+}
+
+fn main() {
+    let a = A::try_from(B { id: Some(1) }).unwrap();
+    let b: B = a.try_into().unwrap();
+    assert_eq!(b.id, Some(1));
+}
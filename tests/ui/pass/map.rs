@@ -0,0 +1,30 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    #[convert(map = ".to_string()")]
+    id: String,
+    #[convert(map = ". as i64")]
+    age: i64,
+    #[convert(map = "format!(\"id: {}, age: {}\", this.id, this.age)")]
+    message: String,
+}
+
+struct B {
+    id: i64,
+    age: u64,
+    message: (),
+}
+
+fn main() {
+    let a: A = B {
+        id: 1,
+        age: 2,
+        message: (),
+    }
+    .into();
+    assert_eq!(a.id, "1");
+    assert_eq!(a.age, 2);
+    assert_eq!(a.message, "id: 1, age: 2");
+}
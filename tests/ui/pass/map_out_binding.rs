@@ -0,0 +1,25 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+struct A {
+    // `out` here is a closure parameter, not the converted target
+    #[convert(map = ".iter().map(|out| out.len()).sum()")]
+    total: usize,
+    name: String,
+}
+
+struct B {
+    total: Vec<String>,
+    name: String,
+}
+
+fn main() {
+    let a: A = B {
+        total: vec!["ab".to_string(), "c".to_string()],
+        name: "x".to_string(),
+    }
+    .into();
+    assert_eq!(a.total, 3);
+    assert_eq!(a.name, "x");
+}
@@ -0,0 +1,19 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(into(B), patch(APatch))]
+struct A {
+    #[convert(map = ".to_string()")]
+    num: i64,
+}
+
+struct B {
+    num: String,
+}
+
+fn main() {
+    let mut a = A { num: 1 };
+    a.apply_patch(APatch { num: Some(2) });
+    let b: B = a.into();
+    assert_eq!(b.num, "2");
+}
@@ -0,0 +1,18 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from_into(B))]
+struct A {
+    #[convert(rename = user_id)]
+    id: i64,
+}
+
+struct B {
+    user_id: i64,
+}
+
+fn main() {
+    let a: A = B { user_id: 1 }.into();
+    let b: B = a.into();
+    assert_eq!(b.user_id, 1);
+}
@@ -0,0 +1,28 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert, Default)]
+#[convert(from(B))]
+#[convert(from_into(C, default))]
+struct A {
+    id: i64,
+    #[convert(skip)]
+    age: i64,
+}
+
+struct B {
+    id: i64,
+}
+
+#[derive(Default)]
+struct C {
+    id: i64,
+    name: String,
+}
+
+fn main() {
+    let a: A = B { id: 1 }.into();
+    assert_eq!((a.id, a.age), (1, 0));
+
+    let c: C = a.into();
+    assert_eq!((c.id, c.name.as_str()), (1, ""));
+}
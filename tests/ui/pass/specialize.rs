@@ -0,0 +1,29 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+#[convert(from_into(self::C))]
+struct A {
+    #[convert(rename(from_into(C, c_id)))]
+    #[convert(rename(from = user_id))]
+    id: String,
+    #[convert(skip)]
+    age: i64,
+}
+
+struct B {
+    user_id: String,
+}
+
+struct C {
+    c_id: String,
+}
+
+fn main() {
+    let a: A = B {
+        user_id: "1".to_string(),
+    }
+    .into();
+    let c: C = a.into();
+    assert_eq!(c.c_id, "1");
+}
@@ -0,0 +1,42 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B))]
+#[convert(try_from(C))]
+struct A {
+    #[convert(unwrap(from(B, expect), from(C, required)))]
+    id: i64,
+    #[convert(unwrap = default)]
+    name: String,
+    #[convert(unwrap_or = "\"guest\".to_string()")]
+    role: String,
+}
+
+struct B {
+    id: Option<i64>,
+    name: Option<String>,
+    role: Option<String>,
+}
+
+struct C {
+    id: Option<i64>,
+    name: Option<String>,
+    role: Option<String>,
+}
+
+fn main() {
+    let a: A = B {
+        id: Some(1),
+        name: None,
+        role: None,
+    }
+    .into();
+    assert_eq!((a.id, a.name.as_str(), a.role.as_str()), (1, "", "guest"));
+
+    let err = A::try_from(C {
+        id: None,
+        name: None,
+        role: None,
+    });
+    assert!(err.is_err());
+}
@@ -0,0 +1,31 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from(B, wrap_option))]
+#[convert(into(B, unwrap_option))]
+struct A {
+    id: Option<i64>,
+}
+
+#[derive(JustConvert)]
+#[convert(into(D, wrap_option))]
+#[convert(from(D, unwrap_option))]
+struct B {
+    id: i64,
+}
+
+struct D {
+    id: Option<i64>,
+}
+
+fn main() {
+    let a: A = B { id: 1 }.into();
+    assert_eq!(a.id, Some(1));
+    let b: B = A { id: None }.into();
+    assert_eq!(b.id, 0);
+
+    let d: D = b.into();
+    assert_eq!(d.id, Some(0));
+    let b: B = D { id: None }.into();
+    assert_eq!(b.id, 0);
+}
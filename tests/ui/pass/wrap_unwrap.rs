@@ -0,0 +1,33 @@
+use just_convert::JustConvert;
+
+#[derive(JustConvert)]
+#[convert(from_into(B))]
+struct A {
+    #[convert(wrap(from), unwrap(into))]
+    age: Option<i64>,
+}
+
+struct B {
+    age: i64,
+}
+
+#[derive(JustConvert)]
+#[convert(into(D))]
+struct C {
+    #[convert(wrap)]
+    age: i64,
+}
+
+struct D {
+    age: Option<i64>,
+}
+
+fn main() {
+    let a: A = B { age: 1 }.into();
+    assert_eq!(a.age, Some(1));
+    let b: B = A { age: None }.into();
+    assert_eq!(b.age, 0);
+
+    let d: D = C { age: 2 }.into();
+    assert_eq!(d.age, Some(2));
+}